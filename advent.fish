@@ -3,10 +3,10 @@
 function advent -a year -a day
     # All in one launcher
     export RUST_LOG=debug
-    set --local advent_bin (string join _ advent $year $day)
+    set --local advent_module (printf "advent_%04d_%02d::" $year $day)
     cargo run --bin advent -- prepare $year $day
     open https://adventofcode.com/$year/day/$day
-    cargo watch --clear --exec "check --quiet" --exec "test --bin $advent_bin" --exec "run --quiet --bin $advent_bin"
+    cargo watch --clear --exec "check --quiet" --exec "test --lib $advent_module" --exec "run --quiet --bin advent -- run $year $day"
 end
 
 advent $argv
\ No newline at end of file
@@ -0,0 +1,43 @@
+//! Declares the modules of `src/advent_of_code/solutions`, each behind its `advent_YYYY` feature
+use std::path::Path;
+
+const SOLUTIONS: &str = "src/advent_of_code/solutions";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SOLUTIONS);
+
+    let directory =
+        Path::new(&std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo")).join(SOLUTIONS);
+    let mut solutions = vec![];
+    for entry in std::fs::read_dir(&directory).expect("could not list solutions") {
+        let name = entry.expect("could not list solutions").file_name();
+        let module = match name.to_str().and_then(|name| name.strip_suffix(".rs")) {
+            Some(module) => module.to_string(),
+            None => continue,
+        };
+        let year = module
+            .strip_prefix("advent_")
+            .and_then(|name| name.split_once('_'))
+            .and_then(|(year, day)| Some((year.parse::<usize>().ok()?, day.parse::<usize>().ok()?)))
+            .map(|(year, _)| year);
+        if let Some(year) = year {
+            solutions.push((year, module));
+        }
+    }
+    solutions.sort_unstable();
+
+    let modules = solutions
+        .iter()
+        .map(|(year, module)| {
+            format!(
+                "#[cfg(feature = \"advent_{}\")]\n#[path = {:?}]\nmod {};\n",
+                year,
+                directory.join(format!("{}.rs", module)),
+                module
+            )
+        })
+        .collect::<String>();
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    std::fs::write(Path::new(&out_dir).join("solutions.rs"), modules)
+        .expect("could not write the list of solutions");
+}
@@ -0,0 +1,129 @@
+//! Format patterns of `#[advent(format = "...")]`
+use syn::{Error, LitStr, Result};
+
+/// Piece of a format pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Text expected verbatim in the input
+    Literal(String),
+    /// Field parsed from the input, by name or by position
+    Field(String),
+}
+
+/// Split a pattern like `{x1},{y1} -> {x2},{y2}` into its segments
+///
+/// Braces are escaped by doubling them. Two fields must be separated by a literal, otherwise
+/// there is no way to know where the first one ends.
+pub fn parse(pattern: &LitStr) -> Result<Vec<Segment>> {
+    let value = pattern.value();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(Error::new_spanned(pattern, "unclosed `{` in format")),
+                    }
+                }
+                let field = field.trim().to_string();
+                if field.is_empty() {
+                    return Err(Error::new_spanned(
+                        pattern,
+                        "empty `{}` in format, name the field it parses",
+                    ));
+                }
+
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if let Some(Segment::Field(previous)) = segments.last() {
+                    return Err(Error::new_spanned(
+                        pattern,
+                        format!(
+                            "fields `{}` and `{}` must be separated by some text",
+                            previous, field
+                        ),
+                    ));
+                }
+                segments.push(Segment::Field(field));
+            }
+            '}' => {
+                return Err(Error::new_spanned(
+                    pattern,
+                    "unmatched `}` in format, use `}}` for a literal brace",
+                ))
+            }
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(pattern: &str) -> Result<Vec<Segment>> {
+        parse(&LitStr::new(pattern, proc_macro2::Span::call_site()))
+    }
+
+    fn error(pattern: &str) -> String {
+        segments(pattern).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn split_pattern() {
+        assert_eq!(
+            segments("{x1},{ y1 } -> {0}").unwrap(),
+            [
+                Segment::Field("x1".into()),
+                Segment::Literal(",".into()),
+                Segment::Field("y1".into()),
+                Segment::Literal(" -> ".into()),
+                Segment::Field("0".into()),
+            ]
+        );
+        assert_eq!(
+            segments("{{{0}}}").unwrap(),
+            [
+                Segment::Literal("{".into()),
+                Segment::Field("0".into()),
+                Segment::Literal("}".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn refuse_invalid_patterns() {
+        assert_eq!(error("{x"), "unclosed `{` in format");
+        assert_eq!(
+            error("x = {}"),
+            "empty `{}` in format, name the field it parses"
+        );
+        assert_eq!(
+            error("{x}{y}"),
+            "fields `x` and `y` must be separated by some text"
+        );
+        assert_eq!(
+            error("x}"),
+            "unmatched `}` in format, use `}}` for a literal brace"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! Derive macros of the `puzzling` crate, re-exported by `puzzling::advent_of_code`
+mod format;
+
+use std::collections::HashSet;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, LitStr, Result};
+
+use format::Segment;
+
+const MISSING_FORMAT: &str = "missing #[advent(format = \"...\")]";
+
+/// Implement `FromStr` from a format pattern describing a line of input
+///
+/// Fields are named between braces (`{0}` for tuple fields) and parsed with their own `FromStr`,
+/// everything else must appear verbatim. Enums give a pattern per variant, the first one
+/// matching is used.
+///
+/// ```ignore
+/// #[derive(AdventInput)]
+/// #[advent(format = "{x1},{y1} -> {x2},{y2}")]
+/// struct Line {
+///     x1: usize,
+///     y1: usize,
+///     x2: usize,
+///     y2: usize,
+/// }
+///
+/// #[derive(AdventInput)]
+/// enum Command {
+///     #[advent(format = "forward {0}")]
+///     Forward(isize),
+///     #[advent(format = "down {0}")]
+///     Down(isize),
+/// }
+/// ```
+#[proc_macro_derive(AdventInput, attributes(advent))]
+pub fn derive_advent_input(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let type_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let pattern = format_attribute(&input.attrs)?
+                .ok_or_else(|| Error::new_spanned(name, MISSING_FORMAT))?;
+            let parser = parse_fields(&pattern, &data.fields, quote!(Self))?;
+            quote! {
+                (|| -> ::puzzling::prelude::Result<Self> { #parser })()
+                    .with_context(|| format!("`{}` does not match the format of {}", s, #type_name))
+            }
+        }
+        Data::Enum(data) => {
+            if let Some(pattern) = format_attribute(&input.attrs)? {
+                return Err(Error::new_spanned(
+                    pattern,
+                    "enums take a format on each of their variants",
+                ));
+            }
+
+            let attempts = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let variant_name = ident.to_string();
+                    let pattern = format_attribute(&variant.attrs)?
+                        .ok_or_else(|| Error::new_spanned(ident, MISSING_FORMAT))?;
+                    let parser = parse_fields(&pattern, &variant.fields, quote!(Self::#ident))?;
+                    Ok(quote! {
+                        match (|| -> ::puzzling::prelude::Result<Self> { #parser })() {
+                            Ok(value) => return Ok(value),
+                            Err(e) => __errors.push(format!("{}: {:#}", #variant_name, e)),
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            quote! {
+                let mut __errors: Vec<String> = Vec::new();
+                #(#attempts)*
+                Err(::puzzling::prelude::anyhow!(
+                    "`{}` matches no format of {}:\n  {}",
+                    s,
+                    #type_name,
+                    __errors.join("\n  ")
+                ))
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                name,
+                "AdventInput cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::puzzling::prelude::Error;
+
+            fn from_str(s: &str) -> ::puzzling::prelude::Result<Self> {
+                #[allow(unused_imports)]
+                use ::puzzling::prelude::Context;
+
+                #body
+            }
+        }
+    })
+}
+
+/// Pattern given by `#[advent(format = "...")]`, if any
+fn format_attribute(attrs: &[Attribute]) -> Result<Option<LitStr>> {
+    let mut pattern = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("advent")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                pattern = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown advent attribute, expected `format`"))
+            }
+        })?;
+    }
+    Ok(pattern)
+}
+
+/// Statements parsing `s` into `constructor`, following the pattern segment by segment
+fn parse_fields(
+    pattern: &LitStr,
+    fields: &Fields,
+    constructor: TokenStream2,
+) -> Result<TokenStream2> {
+    let segments = format::parse(pattern)?;
+
+    // Name of each field in the pattern, its position for tuple fields
+    let members = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let name = match &field.ident {
+                Some(ident) => ident.unraw().to_string(),
+                None => i.to_string(),
+            };
+            (name, field)
+        })
+        .collect::<Vec<_>>();
+    let local = |name: &str| format_ident!("__field_{}", name);
+
+    let mut used = HashSet::new();
+    let mut steps = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(text) => steps.push(quote! {
+                let __rest = ::puzzling::advent_of_code::parsing::format_literal(__rest, #text)?;
+            }),
+            Segment::Field(name) => {
+                let (_, field) = members
+                    .iter()
+                    .find(|(member, _)| member == name)
+                    .ok_or_else(|| {
+                        let known = members.iter().map(|(member, _)| member.as_str());
+                        Error::new_spanned(
+                            pattern,
+                            format!(
+                                "unknown field `{}` in format, expected one of: {}",
+                                name,
+                                known.collect::<Vec<_>>().join(", ")
+                            ),
+                        )
+                    })?;
+                if !used.insert(name.clone()) {
+                    return Err(Error::new_spanned(
+                        pattern,
+                        format!("field `{}` appears twice in format", name),
+                    ));
+                }
+
+                let ty = &field.ty;
+                let until = match segments.get(i + 1) {
+                    Some(Segment::Literal(text)) => quote!(Some(#text)),
+                    _ => quote!(None),
+                };
+                let local = local(name);
+                steps.push(quote! {
+                    let (#local, __rest) = ::puzzling::advent_of_code::parsing::format_field::<#ty>(
+                        __rest, #name, #until,
+                    )?;
+                });
+            }
+        }
+    }
+
+    if let Some((name, _)) = members.iter().find(|(name, _)| !used.contains(name)) {
+        return Err(Error::new_spanned(
+            pattern,
+            format!("field `{}` is missing from format", name),
+        ));
+    }
+
+    let value = match fields {
+        Fields::Named(_) => {
+            let assignments = members.iter().map(|(name, field)| {
+                let member = &field.ident;
+                let local = local(name);
+                quote!(#member: #local)
+            });
+            quote!(#constructor { #(#assignments),* })
+        }
+        Fields::Unnamed(_) => {
+            let locals = members.iter().map(|(name, _)| local(name));
+            quote!(#constructor(#(#locals),*))
+        }
+        Fields::Unit => constructor,
+    };
+
+    Ok(quote! {
+        let __rest = s;
+        #(#steps)*
+        ::puzzling::advent_of_code::parsing::format_end(__rest)?;
+        Ok(#value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: &str) -> String {
+        let input = syn::parse_str::<DeriveInput>(input).unwrap();
+        expand(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn expand_formats() {
+        let input = syn::parse_str::<DeriveInput>(
+            r#"
+            enum Command {
+                #[advent(format = "forward {0}")]
+                Forward(isize),
+                #[advent(format = "stop")]
+                Stop,
+            }
+            "#,
+        )
+        .unwrap();
+        let expanded = expand(&input).unwrap().to_string();
+        assert!(expanded.contains("FromStr for Command"));
+        assert!(expanded.contains("format_field"));
+        assert!(expanded.contains("Self :: Stop"));
+    }
+
+    #[test]
+    fn refuse_unknown_field() {
+        assert_eq!(
+            error(r#"#[advent(format = "{x},{z}")] struct Point { x: i32, y: i32 }"#),
+            "unknown field `z` in format, expected one of: x, y"
+        );
+    }
+
+    #[test]
+    fn refuse_adjacent_fields() {
+        assert_eq!(
+            error(r#"#[advent(format = "{x}{y}")] struct Point { x: i32, y: i32 }"#),
+            "fields `x` and `y` must be separated by some text"
+        );
+    }
+
+    #[test]
+    fn refuse_incomplete_formats() {
+        assert_eq!(
+            error(r#"#[advent(format = "{x}")] struct Point { x: i32, y: i32 }"#),
+            "field `y` is missing from format"
+        );
+        assert_eq!(
+            error(r#"#[advent(format = "{x},{x}")] struct Point { x: i32 }"#),
+            "field `x` appears twice in format"
+        );
+    }
+
+    #[test]
+    fn refuse_missing_formats() {
+        assert_eq!(error("struct Point { x: i32 }"), MISSING_FORMAT);
+        assert_eq!(
+            error(r#"enum Command { #[advent(format = "up {0}")] Up(isize), Down(isize) }"#),
+            MISSING_FORMAT
+        );
+        assert_eq!(
+            error(r#"#[advent(format = "{0}")] enum Command { Up(isize) }"#),
+            "enums take a format on each of their variants"
+        );
+        assert_eq!(
+            error(r#"#[advent(pattern = "{x}")] struct Point { x: i32 }"#),
+            "unknown advent attribute, expected `format`"
+        );
+    }
+}
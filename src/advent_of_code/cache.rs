@@ -0,0 +1,365 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::session::fingerprint;
+use crate::config::{active_profile, Settings, DEFAULT_BASE_URL};
+use crate::prelude::*;
+
+/// Sub-directory of the cache holding entries that expire
+const VOLATILE: &str = "volatile";
+
+/// On disk storage of everything downloaded from the website
+///
+/// Puzzle inputs never change once published so they are kept forever, only the entries
+/// stored through [Cache::read_volatile] and [Cache::write_volatile] expire.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    root: PathBuf,
+}
+
+/// A file stored in the cache
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    /// Path relative to the cache root
+    pub path: PathBuf,
+    pub size: u64,
+    pub volatile: bool,
+}
+
+impl Cache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Cache of the account configured in `puzzling.toml`
+    ///
+    /// Located in `cache_dir` if configured, in the XDG cache directory otherwise, and
+    /// namespaced by account so that inputs of different accounts never mix (see
+    /// [account_namespace]). Profiles keep their cache when their cookie is rotated.
+    pub fn from_settings() -> Self {
+        Self::for_profile(active_profile().as_deref())
+    }
+
+    /// Cache of a given profile, `None` for the top level session
+    pub fn for_profile(profile: Option<&str>) -> Self {
+        let settings = &Settings::get().advent_of_code;
+        let namespace = account_namespace(profile, &settings.session_secret, &settings.base_url());
+        Self::new(Self::base_dir().join(namespace))
+    }
+
+    /// Directory holding the cache of every account
+    pub fn base_dir() -> PathBuf {
+        match &Settings::get().advent_of_code.cache_dir {
+            Some(directory) => PathBuf::from(directory),
+            None => dirs::cache_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join("puzzling"),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Location of the input of a day, relative to the cache root
+    pub fn challenge_path(year: usize, day: usize) -> PathBuf {
+        Path::new(&year.to_string())
+            .join(day.to_string())
+            .with_extension("txt")
+    }
+
+    /// Read a persistent entry
+    pub fn read(&self, path: impl AsRef<Path>) -> Result<String> {
+        let path = self.root.join(path);
+        debug!("Looking for cache file: {:?}", path);
+        if !path.exists() {
+            return Err(anyhow!("Cache miss"));
+        }
+        std::fs::read_to_string(path).context("Error reading cache contents")
+    }
+
+    /// Write a persistent entry, replacing any previous value
+    pub fn write(&self, path: impl AsRef<Path>, contents: &str) -> Result<()> {
+        let path = self.root.join(path);
+
+        // Create all sub dirs
+        let parent = path
+            .parent()
+            .ok_or(anyhow!("could not traverse path"))
+            .context("Looking for cache directory")?;
+        std::fs::create_dir_all(parent).context("Creating all needed sub-directory for cache")?;
+
+        std::fs::write(path, contents).context("Writing cache content")
+    }
+
+    /// Read a volatile entry, unless it is older than `ttl`
+    pub fn read_volatile(&self, key: &str, ttl: Duration) -> Result<String> {
+        let path = Path::new(VOLATILE).join(key);
+        let full_path = self.root.join(&path);
+        if !full_path.exists() {
+            return Err(anyhow!("Cache miss"));
+        }
+
+        let cache_age = std::fs::metadata(&full_path)
+            .context("Error fetching cache metadata")?
+            .modified()?
+            .elapsed()?;
+
+        if cache_age < ttl {
+            debug!("Cache hit: {}s", cache_age.as_secs());
+            self.read(path)
+        } else {
+            warn!("Cache is cold");
+            Err(anyhow!("Cache invalidated"))
+        }
+    }
+
+    /// Write a volatile entry
+    pub fn write_volatile(&self, key: &str, contents: &str) -> Result<()> {
+        self.write(Path::new(VOLATILE).join(key), contents)
+    }
+
+    /// Every file of the cache, sorted by path
+    pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let mut entries = vec![];
+        if self.root.exists() {
+            self.collect_entries(&self.root, &mut entries)?;
+        }
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(entries)
+    }
+
+    fn collect_entries(&self, directory: &Path, entries: &mut Vec<CacheEntry>) -> Result<()> {
+        for entry in std::fs::read_dir(directory).context("Listing cache directory")? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                self.collect_entries(&path, entries)?;
+                continue;
+            }
+            let path = path.strip_prefix(&self.root)?.to_path_buf();
+            entries.push(CacheEntry {
+                volatile: path.starts_with(VOLATILE),
+                size: entry.metadata()?.len(),
+                path,
+            });
+        }
+        Ok(())
+    }
+
+    /// Remove inputs and volatile entries, returns the number of files removed
+    ///
+    /// Everything else (answer ledgers, leaderboard snapshots, ...) cannot be downloaded again
+    /// and is always kept.
+    pub fn purge(&self, volatile_only: bool, year: Option<usize>) -> Result<usize> {
+        let year = year.map(|y| y.to_string());
+        let mut removed = 0;
+        for entry in self.entries()? {
+            if !entry.volatile && (volatile_only || entry.challenge().is_none()) {
+                continue;
+            }
+            if let Some(year) = &year {
+                if !entry.path.starts_with(year) {
+                    continue;
+                }
+            }
+            std::fs::remove_file(self.root.join(&entry.path)).context("Removing cache entry")?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Bundle all persistent entries in a single JSON file
+    pub fn export(&self, destination: impl AsRef<Path>) -> Result<usize> {
+        let bundle = self
+            .entries()?
+            .into_iter()
+            .filter(|entry| !entry.volatile)
+            .map(|entry| -> Result<_> {
+                let contents = self.read(&entry.path)?;
+                Ok((entry.path.to_string_lossy().to_string(), contents))
+            })
+            .collect::<Result<BTreeMap<String, String>>>()?;
+
+        let contents = serde_json::to_string_pretty(&bundle).context("Serializing cache")?;
+        std::fs::write(destination, contents).context("Writing cache export")?;
+        Ok(bundle.len())
+    }
+
+    /// Restore entries from a file written by [Cache::export]
+    ///
+    /// Existing entries are only replaced when `overwrite` is set.
+    pub fn import(&self, source: impl AsRef<Path>, overwrite: bool) -> Result<usize> {
+        let contents = std::fs::read_to_string(source).context("Reading cache export")?;
+        let bundle: BTreeMap<String, String> =
+            serde_json::from_str(&contents).context("Deserializing cache export")?;
+
+        let mut imported = 0;
+        for (path, contents) in bundle {
+            let path = PathBuf::from(path);
+            if path.is_absolute() || path.components().any(|c| c.as_os_str() == "..") {
+                warn!("Skipping suspicious path in cache export: {:?}", path);
+                continue;
+            }
+            if !overwrite && self.root.join(&path).exists() {
+                continue;
+            }
+            self.write(&path, &contents)?;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+}
+
+impl CacheEntry {
+    /// Year and day of the input stored in this entry, `None` for any other entry
+    pub fn challenge(&self) -> Option<(usize, usize)> {
+        let year = self.path.parent()?.to_str()?.parse().ok()?;
+        let day = self
+            .path
+            .file_name()?
+            .to_str()?
+            .strip_suffix(".txt")?
+            .parse()
+            .ok()?;
+        Some((year, day))
+    }
+}
+
+/// Directory name of a profile, `default` for the top level session
+///
+/// Another website (e.g. `advent mock`) gets its own directories, suffixed by its host, so that
+/// its fixtures never pass for real inputs.
+pub(crate) fn profile_namespace(profile: Option<&str>, base_url: &str) -> String {
+    let name = match profile {
+        Some(profile) => format!("profile-{}", profile),
+        None => "default".into(),
+    };
+    with_host(name, base_url)
+}
+
+/// Directory name of an account: its profile, or the fingerprint of the top level session
+///
+/// Sessions without a profile can only be told apart by their secret, so switching
+/// `session_secret` never mixes the inputs of two accounts.
+pub(crate) fn account_namespace(profile: Option<&str>, secret: &str, base_url: &str) -> String {
+    let name = match profile {
+        Some(profile) => format!("profile-{}", profile),
+        None if secret.is_empty() => "default".into(),
+        None => format!("default-{}", fingerprint(secret)),
+    };
+    with_host(name, base_url)
+}
+
+fn with_host(name: String, base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    if base_url == DEFAULT_BASE_URL {
+        return name;
+    }
+
+    let host = base_url
+        .split_once("://")
+        .map_or(base_url, |(_, host)| host)
+        .replace(|c: char| !c.is_ascii_alphanumeric() && c != '.', "-");
+    format!("{}@{}", name, host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::ledger::AnswerLedger;
+    use crate::advent_of_code::testing::scratch;
+    use test_log::test;
+
+    fn cache(name: &str) -> Cache {
+        Cache::new(scratch(name))
+    }
+
+    #[test]
+    fn volatile_entries_expire() {
+        let cache = cache("volatile_entries_expire");
+        cache.write_volatile("leaderboard", "{}").unwrap();
+
+        assert!(cache
+            .read_volatile("leaderboard", Duration::from_secs(60))
+            .is_ok());
+        assert!(cache.read_volatile("leaderboard", Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn purge_volatile_only() {
+        let cache = cache("purge_volatile_only");
+        cache.write(Cache::challenge_path(2021, 1), "199").unwrap();
+        cache.write_volatile("leaderboard", "{}").unwrap();
+
+        assert_eq!(cache.purge(true, None).unwrap(), 1);
+        assert!(cache.read(Cache::challenge_path(2021, 1)).is_ok());
+    }
+
+    #[test]
+    fn purge_keeps_answers_and_history() {
+        let cache = cache("purge_keeps_answers_and_history");
+        cache.write(Cache::challenge_path(2021, 1), "199").unwrap();
+        let ledger = AnswerLedger::path_in(&cache, 2021, 1);
+        cache
+            .write(ledger.strip_prefix(cache.root()).unwrap(), "[]")
+            .unwrap();
+        cache
+            .write("2021/leaderboards/42/1638300000.json", "{}")
+            .unwrap();
+        cache.write_volatile("leaderboard", "{}").unwrap();
+
+        assert_eq!(cache.purge(false, Some(2021)).unwrap(), 1);
+        assert_eq!(cache.purge(false, None).unwrap(), 1);
+        assert_eq!(
+            cache
+                .entries()
+                .unwrap()
+                .iter()
+                .map(|entry| entry.path.to_string_lossy().to_string())
+                .collect_vec(),
+            [
+                "2021/1.answers.json",
+                "2021/leaderboards/42/1638300000.json"
+            ]
+        );
+    }
+
+    #[test]
+    fn export_then_import() {
+        let source = cache("export_then_import_source");
+        source.write("2021/1.txt", "199\n200").unwrap();
+        source.write_volatile("leaderboard", "{}").unwrap();
+
+        let bundle = scratch("export_then_import.json");
+        assert_eq!(source.export(&bundle).unwrap(), 1);
+
+        let destination = cache("export_then_import_destination");
+        assert_eq!(destination.import(&bundle, false).unwrap(), 1);
+        assert_eq!(destination.read("2021/1.txt").unwrap(), "199\n200");
+    }
+
+    #[test]
+    fn namespaces_follow_accounts() {
+        assert_eq!(account_namespace(None, "", DEFAULT_BASE_URL), "default");
+        assert_eq!(
+            account_namespace(Some("alice"), "secret", "https://adventofcode.com/"),
+            "profile-alice"
+        );
+        assert_eq!(
+            account_namespace(Some("alice"), "secret", "http://127.0.0.1:8080"),
+            "profile-alice@127.0.0.1-8080"
+        );
+
+        let first = account_namespace(None, "first", DEFAULT_BASE_URL);
+        let second = account_namespace(None, "second", DEFAULT_BASE_URL);
+        assert!(first.starts_with("default-"));
+        assert_ne!(first, second);
+        assert_eq!(first, account_namespace(None, "first", DEFAULT_BASE_URL));
+        assert_eq!(
+            profile_namespace(None, "http://127.0.0.1:8080"),
+            "default@127.0.0.1-8080"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use regex::Regex;
+use reqwest::StatusCode;
+use serde_derive::{Deserialize, Serialize};
+
+use super::cache::Cache;
+use super::leaderboard::days_in_event;
+use super::session::Session;
+use crate::config::Settings;
+use crate::prelude::*;
+
+/// Stars obtained on each day of each event
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Calendar {
+    pub years: BTreeMap<usize, YearProgress>,
+}
+
+/// Stars obtained during an event
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YearProgress {
+    pub total_stars: u32,
+    /// Stars per day, days without stars are omitted
+    pub days: BTreeMap<u32, u8>,
+}
+
+impl Calendar {
+    pub fn stars(&self, year: usize, day: u32) -> u8 {
+        self.years
+            .get(&year)
+            .and_then(|y| y.days.get(&day))
+            .cloned()
+            .unwrap_or(0)
+    }
+}
+
+/// Events list: `<a href="/2021">[2021]</a> <span class="star-count">50*</span>`
+pub fn parse_events(html: &str) -> BTreeMap<usize, u32> {
+    let event: Regex = Regex::new(
+        r#"(?s)<div class="eventlist-event"><a href="/(\d+)">.*?</a>(?:\s*<span class="star-count">(\d+)\*</span>)?"#,
+    )
+    .unwrap();
+    event
+        .captures_iter(html)
+        .filter_map(|c| {
+            let year = c[1].parse().ok()?;
+            let stars = c.get(2).and_then(|s| s.as_str().parse().ok()).unwrap_or(0);
+            Some((year, stars))
+        })
+        .collect()
+}
+
+/// Calendar of an event: `<a aria-label="Day 1, two stars" href="/2021/day/1" ...>`
+pub fn parse_year(html: &str) -> BTreeMap<u32, u8> {
+    let day: Regex = Regex::new(r#"aria-label="Day (\d+)(?:, (one|two) stars?)?""#).unwrap();
+    day.captures_iter(html)
+        .filter_map(|c| {
+            let stars = match c.get(2)?.as_str() {
+                "two" => 2,
+                _ => 1,
+            };
+            Some((c[1].parse().ok()?, stars))
+        })
+        .collect()
+}
+
+fn fetch_page(session: &Session, path: &str) -> Result<String> {
+    let res = session.get(path).context("Failed to send request")?;
+
+    match res.status() {
+        StatusCode::OK => res.text().context("Failed to read response body"),
+        unhandled => match res.error_for_status() {
+            Ok(_) => Err(anyhow!("Expected to fail for status: {:?}", unhandled)),
+            Err(e) => Err(e).with_context(|| format!("Unhandled HTTP error: {:?}", unhandled)),
+        },
+    }
+}
+
+/// Scrape the events page, then the calendar of every event with stars
+pub fn fetch_calendar(session: &Session) -> Result<Calendar> {
+    let mut calendar = Calendar::default();
+    for (year, total_stars) in parse_events(&fetch_page(session, "/events")?) {
+        let days = if total_stars > 0 {
+            parse_year(&fetch_page(session, &format!("/{}", year))?)
+        } else {
+            BTreeMap::new()
+        };
+        calendar
+            .years
+            .insert(year, YearProgress { total_stars, days });
+    }
+    Ok(calendar)
+}
+
+/// Personal calendar, cached as a volatile entry
+pub fn calendar() -> Result<Calendar> {
+    let cache = Cache::from_settings();
+    let ttl = std::time::Duration::from_secs(Settings::get().advent_of_code.cache_time);
+    if let Ok(contents) = cache.read_volatile("calendar.json", ttl) {
+        return serde_json::from_str(&contents).context("Deserializing cached calendar");
+    }
+
+    let calendar = fetch_calendar(&Session::from_settings()?)?;
+    if let Err(e) = cache.write_volatile("calendar.json", &serde_json::to_string(&calendar)?) {
+        warn!("Could not save calendar to cache: {:?}", e);
+    }
+    Ok(calendar)
+}
+
+/// Days with a `advent_YYYY_DD.rs` solution in a directory, enabled by their feature or not
+pub fn local_solutions(directory: impl AsRef<Path>) -> Result<BTreeSet<(usize, u32)>> {
+    let file: Regex = Regex::new(r"^advent_(\d{4})_(\d{2})\.rs$").unwrap();
+    let mut solutions = BTreeSet::new();
+    for entry in std::fs::read_dir(directory).context("Listing solutions")? {
+        let name = entry?.file_name();
+        if let Some(c) = file.captures(&name.to_string_lossy()) {
+            solutions.insert((c[1].parse()?, c[2].parse()?));
+        }
+    }
+    Ok(solutions)
+}
+
+/// Glyph, in paris markup, for a number of stars
+pub fn star_glyph(stars: u8) -> &'static str {
+    match stars {
+        2 => "<bright-yellow>★</>",
+        1 => "<white>★</>",
+        _ => "<dimmed>·</>",
+    }
+}
+
+/// State of a local solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalSolution {
+    Missing,
+    Present,
+    Passing,
+    Failing,
+}
+
+impl LocalSolution {
+    fn glyph(&self) -> &'static str {
+        match self {
+            LocalSolution::Missing => " ",
+            LocalSolution::Present => "○",
+            LocalSolution::Passing => "<green>✓</>",
+            LocalSolution::Failing => "<red>✗</>",
+        }
+    }
+}
+
+/// Star grid of a year, with the state of the local solutions below each day
+pub fn render_year(
+    calendar: &Calendar,
+    year: usize,
+    local: impl Fn(usize, u32) -> LocalSolution,
+) -> String {
+    let days = days_in_event(year);
+    let total = calendar.years.get(&year).map_or(0, |y| y.total_stars);
+    let stars = (1..=days)
+        .map(|day| star_glyph(calendar.stars(year, day)))
+        .join("");
+    let solutions = (1..=days).map(|day| local(year, day).glyph()).join("");
+    format!("{} {} {:>2}*\n     {}", year, stars, total, solutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn parse_events_page() {
+        let html = r#"<div class="eventlist-event"><a href="/2021">[2021]</a> <span class="star-count">34*</span></div>
+<div class="eventlist-event"><a href="/2020">[2020]</a></div>"#;
+        let events = parse_events(html);
+        assert_eq!(events.get(&2021), Some(&34));
+        assert_eq!(events.get(&2020), Some(&0));
+    }
+
+    #[test]
+    fn parse_calendar_page() {
+        let html = r#"<a aria-label="Day 1, two stars" href="/2021/day/1" class="calendar-day1 calendar-verycomplete">
+<a aria-label="Day 2, one star" href="/2021/day/2" class="calendar-day2 calendar-complete">
+<a aria-label="Day 3" href="/2021/day/3" class="calendar-day3">"#;
+        let days = parse_year(html);
+        assert_eq!(days.get(&1), Some(&2));
+        assert_eq!(days.get(&2), Some(&1));
+        assert_eq!(days.get(&3), None);
+    }
+
+    #[test]
+    fn render_grid() {
+        let mut calendar = Calendar::default();
+        calendar.years.insert(
+            2021,
+            YearProgress {
+                total_stars: 3,
+                days: [(1, 2), (2, 1)].into_iter().collect(),
+            },
+        );
+        let grid = render_year(&calendar, 2021, |_, day| match day {
+            1 => LocalSolution::Passing,
+            _ => LocalSolution::Missing,
+        });
+        assert!(grid.starts_with("2021 <bright-yellow>★</><white>★</><dimmed>·</>"));
+        assert!(grid.ends_with(&format!(" 3*\n     <green>✓</>{}", " ".repeat(24))));
+    }
+}
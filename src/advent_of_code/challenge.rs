@@ -1,111 +1,201 @@
-use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
 
-use crate::config::Settings;
-use crate::prelude::*;
-use anyhow::Context;
-use reqwest::{blocking::Client, StatusCode};
+use chrono::{DateTime, TimeZone, Utc};
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::StatusCode;
+use thiserror::Error;
 
-pub fn daily_challenge(year: usize, day: usize) -> Result<String> {
-    if let Ok(challenge) = try_getting_cached_challenge(year, day) {
-        return Ok(challenge);
-    }
-    info!("Cache miss, fetching fresh challenge");
+use super::session::Session;
+use super::source::configured_source;
+use crate::prelude::*;
 
-    let challenge = fetch_daily_challenge(year, day)
-        .with_context(|| format!("Fetching daily challenge of: {}/{}", year, day))?;
-    info!("Challenge fetched: Content-Length: {}", challenge.len());
+/// Reasons for which a daily challenge could not be retrieved
+#[derive(Debug, Error)]
+pub enum ChallengeError {
+    #[error("invalid or expired session, update session_secret in puzzling.toml")]
+    SessionExpired,
+    #[error("input not yet available, unlocks at {unlocks_at}")]
+    NotYetUnlocked { unlocks_at: DateTime<Utc> },
+    #[error("rate limited by the website")]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("network failure")]
+    Network(#[from] reqwest::Error),
+    #[error("cached challenge is corrupted: {0:?}")]
+    CacheCorrupted(std::path::PathBuf),
+    #[error("no input for {year}/{day} in {origin}")]
+    Missing {
+        year: usize,
+        day: usize,
+        origin: String,
+    },
+    #[error("could not decrypt {0:?}, check the vault key")]
+    VaultUnreadable(std::path::PathBuf),
+    #[error("could not read input")]
+    Io(#[from] std::io::Error),
+    #[error("unexpected HTTP status: {0}")]
+    UnexpectedStatus(StatusCode),
+    #[error("invalid input source: {0}")]
+    InvalidSource(String),
+    #[error("{year}/{day} is not a day of Advent of Code")]
+    InvalidDay { year: usize, day: usize },
+    #[error("invalid settings: {0:#}")]
+    Settings(anyhow::Error),
+}
 
-    info!("Writing challenge to cache");
-    if let Err(e) = try_writing_challenge_to_cache(year, day, challenge.clone()) {
-        warn!("Could not save challenge to cache: {:?}", e);
+/// Instant at which a puzzle is published: midnight US Eastern time (UTC-5)
+pub fn unlock_time(year: usize, day: usize) -> Result<DateTime<Utc>, ChallengeError> {
+    if !(1..=25).contains(&day) {
+        return Err(ChallengeError::InvalidDay { year, day });
     }
-
-    Ok(challenge)
+    Utc.with_ymd_and_hms(year as i32, 12, day as u32, 5, 0, 0)
+        .single()
+        .ok_or(ChallengeError::InvalidDay { year, day })
 }
 
-fn challenge_cache_path(year: usize, day: usize) -> PathBuf {
-    std::env::temp_dir()
-        .canonicalize()
-        .unwrap()
-        .join("cache")
-        .join(year.to_string())
-        .join(day.to_string())
-        .with_extension("txt")
+/// Daily challenge from the [ChallengeSource](super::source::ChallengeSource) of the `input`
+/// setting, the website by default
+pub fn daily_challenge(year: usize, day: usize) -> Result<String, ChallengeError> {
+    configured_source(None)?.challenge(year, day)
 }
 
-fn try_getting_cached_challenge(year: usize, day: usize) -> Result<String> {
-    let path = challenge_cache_path(year, day);
-    info!("Looking for cache file: {:?}", path);
-
-    // TODO(caching): check for cache invalidation
-    if !path.exists() {
-        return Err(anyhow!("Cache miss"));
-    }
-
-    let cache_age = std::fs::metadata(path.clone())
-        .context("Error fetching cache metadata")?
-        .modified()?
-        .elapsed()?
-        .as_secs();
-
-    if cache_age < Settings::get().advent_of_code.cache_time {
-        info!("Cache hit: {}s", cache_age);
-        std::fs::read_to_string(path).context("Error reading cache contents")
-    } else {
-        warn!("Cache is cold");
-        Err(anyhow!("Cache invalidated"))
+/// Number of attempts made once the puzzle should be unlocked
+const UNLOCK_RETRIES: u32 = 5;
+
+/// Wait until the puzzle unlocks, then fetch its input as soon as the website serves it
+pub fn daily_challenge_when_available(year: usize, day: usize) -> Result<String, ChallengeError> {
+    wait_until(unlock_time(year, day)?);
+
+    let mut attempt = 0;
+    loop {
+        let error = match daily_challenge(year, day) {
+            Err(error) if attempt < UNLOCK_RETRIES => error,
+            result => return result,
+        };
+        attempt += 1;
+        let delay = match unlock_retry_delay(&error, attempt) {
+            Some(delay) => delay,
+            None => return Err(error),
+        };
+        warn!("Input not served yet, retrying in {}ms", delay.as_millis());
+        std::thread::sleep(delay);
     }
 }
 
-fn try_writing_challenge_to_cache(year: usize, day: usize, contents: String) -> Result<()> {
-    let path = challenge_cache_path(year, day);
-
-    // Create all sub dirs
-    let parent = path
-        .parent()
-        .ok_or(anyhow!("could not traverse path"))
-        .context("Looking for cache directory")?;
-    std::fs::create_dir_all(parent).context("Creating all needed sub-directory for cache")?;
-
-    // Remove previous cache value
-    if path.exists() {
-        std::fs::remove_file(path.clone()).context("Removing invalidated cache")?
+/// Show a countdown until the given instant
+fn wait_until(instant: DateTime<Utc>) {
+    let remaining = match (instant - Utc::now()).to_std() {
+        Ok(remaining) => remaining,
+        // Already in the past
+        Err(_) => return,
     };
+    info!("Puzzle unlocks at {}", instant);
+
+    let countdown = ProgressBar::new(remaining.as_secs());
+    countdown.set_style(
+        ProgressStyle::with_template("Unlocking in {eta_precise} [{wide_bar}]")
+            .expect("valid progress template"),
+    );
+    while let Ok(remaining) = (instant - Utc::now()).to_std() {
+        countdown.set_position(
+            countdown
+                .length()
+                .unwrap_or(0)
+                .saturating_sub(remaining.as_secs()),
+        );
+        std::thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    countdown.finish_and_clear();
+}
 
-    // Write new contents
-    std::fs::write(path, contents).context("Writing cache content")?;
+/// Delay before retrying after an error, `None` if retrying is pointless
+///
+/// A rate limited attempt waits at least as long as the website asked.
+fn unlock_retry_delay(error: &ChallengeError, attempt: u32) -> Option<Duration> {
+    match error {
+        ChallengeError::NotYetUnlocked { .. } => Some(retry_delay(attempt)),
+        ChallengeError::RateLimited { retry_after } => {
+            Some(retry_delay(attempt).max(retry_after.unwrap_or_default()))
+        }
+        _ => None,
+    }
+}
 
-    Ok(())
+/// Exponential delay between attempts, with up to a second of jitter to spread the load
+fn retry_delay(attempt: u32) -> Duration {
+    let jitter = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_millis();
+    Duration::from_millis(500 * 2u64.pow(attempt - 1) + jitter as u64)
 }
 
-fn fetch_daily_challenge(year: usize, day: usize) -> Result<String> {
-    let res = Client::new()
-        .get(format!(
-            "https://adventofcode.com/{}/day/{}/input",
-            year, day
-        ))
-        .header(
-            reqwest::header::COOKIE,
-            format!("session={}", Settings::get().advent_of_code.session_secret),
-        )
-        .send()
-        .context("Failed to send request")?;
+pub(crate) fn fetch_daily_challenge(
+    session: &Session,
+    year: usize,
+    day: usize,
+) -> Result<String, ChallengeError> {
+    let res = session.get(&format!("{}/day/{}/input", year, day))?;
 
     match res.status() {
-        StatusCode::OK => res.text().context("Failed to read response body"),
-        StatusCode::NOT_FOUND => Err({
-            let text = res.text();
-            if text.is_ok() && text.unwrap().contains("Please log in") {
+        StatusCode::OK => Ok(res.text()?),
+        StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND => {
+            let text = res.text()?;
+            if text.contains("Please log in") {
                 error!("Invalid or expired session");
-                anyhow!("Invalid or expired session")
+                Err(ChallengeError::SessionExpired)
             } else {
                 warn!("Input not yet available");
-                anyhow!("Input not yet available")
+                Err(ChallengeError::NotYetUnlocked {
+                    unlocks_at: unlock_time(year, day)?,
+                })
             }
+        }
+        StatusCode::TOO_MANY_REQUESTS => Err(ChallengeError::RateLimited {
+            retry_after: res
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok()?.parse().ok())
+                .map(Duration::from_secs),
         }),
-        unhandled => match res.error_for_status() {
-            Ok(_) => Err(anyhow!("Expected to fail for status: {:?}", unhandled)),
-            Err(e) => Err(e).with_context(|| format!("Unhandled HTTP error: {:?}", unhandled)),
-        },
+        unhandled => Err(ChallengeError::UnexpectedStatus(unhandled)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn unlock_time_of_invalid_days() {
+        assert_eq!(
+            unlock_time(2021, 1).unwrap().to_rfc3339(),
+            "2021-12-01T05:00:00+00:00"
+        );
+        assert!(matches!(
+            unlock_time(2021, 0),
+            Err(ChallengeError::InvalidDay { .. })
+        ));
+        assert!(matches!(
+            unlock_time(2021, 40),
+            Err(ChallengeError::InvalidDay {
+                year: 2021,
+                day: 40
+            })
+        ));
+    }
+
+    #[test]
+    fn honour_retry_after() {
+        let retry_after = Some(Duration::from_secs(30));
+        assert!(
+            unlock_retry_delay(&ChallengeError::RateLimited { retry_after }, 1).unwrap()
+                >= Duration::from_secs(30)
+        );
+        assert!(
+            unlock_retry_delay(&ChallengeError::RateLimited { retry_after: None }, 1).unwrap()
+                < Duration::from_secs(2)
+        );
+        assert!(unlock_retry_delay(&ChallengeError::SessionExpired, 1).is_none());
     }
 }
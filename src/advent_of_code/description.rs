@@ -0,0 +1,246 @@
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use reqwest::StatusCode;
+
+use super::cache::Cache;
+use super::calendar;
+use super::ledger::AnswerLedger;
+use super::puzzle::Part;
+use super::session::Session;
+use crate::prelude::*;
+
+/// Statement of a daily puzzle, one HTML `<article>` per unlocked part
+#[derive(Debug, Clone, Default)]
+pub struct Description {
+    pub articles: Vec<String>,
+}
+
+impl Description {
+    /// Extract the puzzle articles from a puzzle page
+    pub fn from_html(html: &str) -> Self {
+        let article: Regex =
+            Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+        Self {
+            articles: article
+                .captures_iter(html)
+                .map(|c| c[1].to_string())
+                .collect(),
+        }
+    }
+
+    /// Statement of a part, if unlocked
+    pub fn part(&self, part: Part) -> Option<&str> {
+        self.articles
+            .get(part.level() as usize - 1)
+            .map(String::as_str)
+    }
+
+    /// The articles wrapped back as they appear on the website, used for caching
+    pub fn to_html(&self) -> String {
+        self.articles
+            .iter()
+            .map(|a| format!("<article class=\"day-desc\">{}</article>", a))
+            .join("\n")
+    }
+}
+
+/// Location of the cached description of a day, relative to the cache root
+pub fn description_path(year: usize, day: usize) -> PathBuf {
+    Path::new(&year.to_string()).join(format!("{}.description.html", day))
+}
+
+/// Description of a day, from the cache when possible
+///
+/// A cached description missing part two is refreshed once part one is known to be solved.
+pub fn puzzle_description(year: usize, day: usize) -> Result<Description> {
+    let cache = Cache::from_settings();
+    let path = description_path(year, day);
+
+    if let Ok(html) = cache.read(&path) {
+        let description = Description::from_html(&html);
+        if description.articles.len() >= 2 || !part_one_solved(year, day)? {
+            return Ok(description);
+        }
+        info!("Part one solved, refreshing description");
+    }
+
+    let description = fetch_description(&Session::from_settings()?, year, day)
+        .with_context(|| format!("Fetching description of: {}/{}", year, day))?;
+    if let Err(e) = cache.write(&path, &description.to_html()) {
+        warn!("Could not save description to cache: {:?}", e);
+    }
+    Ok(description)
+}
+
+/// Whether part one is solved, in the ledger or on the website (it may be solved from elsewhere)
+fn part_one_solved(year: usize, day: usize) -> Result<bool> {
+    if AnswerLedger::open(year, day)?.solution(Part::One).is_some() {
+        return Ok(true);
+    }
+    match calendar::calendar() {
+        Ok(calendar) => Ok(calendar.stars(year, day as u32) > 0),
+        Err(e) => {
+            warn!("Could not check the stars of {}/{}: {:?}", year, day, e);
+            Ok(false)
+        }
+    }
+}
+
+pub fn fetch_description(session: &Session, year: usize, day: usize) -> Result<Description> {
+    let res = session
+        .get(&format!("{}/day/{}", year, day))
+        .context("Failed to send request")?;
+
+    match res.status() {
+        StatusCode::OK => Ok(Description::from_html(
+            &res.text().context("Failed to read response body")?,
+        )),
+        StatusCode::NOT_FOUND => Err(anyhow!("Puzzle not yet available")),
+        unhandled => match res.error_for_status() {
+            Ok(_) => Err(anyhow!("Expected to fail for status: {:?}", unhandled)),
+            Err(e) => Err(e).with_context(|| format!("Unhandled HTTP error: {:?}", unhandled)),
+        },
+    }
+}
+
+/// Decode the few HTML entities used by the website
+pub(crate) fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Stand-ins for the angle brackets of the text (e.g. the chunks of 2021 day 10), which paris
+/// would read as tags
+const OPENING_BRACKET: char = '\u{E000}';
+const CLOSING_BRACKET: char = '\u{E001}';
+
+/// Decode the text of an article, then escape it for paris
+fn escape_text(text: &str) -> String {
+    decode_entities(text)
+        .replace('<', &OPENING_BRACKET.to_string())
+        .replace('>', &CLOSING_BRACKET.to_string())
+}
+
+/// Render an article for the terminal, colors included
+pub fn render_terminal(article: &str) -> String {
+    paris::formatter::colorize_string(render(article))
+        .replace(OPENING_BRACKET, "<")
+        .replace(CLOSING_BRACKET, ">")
+}
+
+/// Translate an article into paris markup for the terminal
+///
+/// Angle brackets of the text are escaped, use [render_terminal] to print it.
+pub fn render(article: &str) -> String {
+    let tag: Regex = Regex::new(r"<(/?)([a-zA-Z0-9]+)([^>]*)>").unwrap();
+    let whitespace: Regex = Regex::new(r"\s+").unwrap();
+
+    let mut output = String::new();
+    let mut in_pre = false;
+    let mut last = 0;
+    for captures in tag.captures_iter(article) {
+        let whole = captures.get(0).unwrap();
+
+        // Text before the tag
+        let text = &article[last..whole.start()];
+        last = whole.end();
+        if in_pre {
+            output.push_str(&escape_text(text).replace('\n', "\n    "));
+        } else {
+            output.push_str(&escape_text(&whitespace.replace_all(text, " ")));
+        }
+
+        let closing = &captures[1] == "/";
+        let attributes = &captures[3];
+        match (&captures[2], closing) {
+            ("h2", false) => output.push_str("<bold><bright-white>"),
+            ("h2", true) => output.push_str("</>\n\n"),
+            ("p", true) => output.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                output.push_str("<cyan>    ");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                output = output.trim_end().to_string();
+                output.push_str("</>\n\n");
+            }
+            ("code", false) if !in_pre => output.push_str("<cyan>"),
+            ("code", true) if !in_pre => output.push_str("</>"),
+            ("em", false) if attributes.contains("star") => output.push_str("<bright-yellow>"),
+            ("em", false) => output.push_str("<bold><bright-white>"),
+            ("em", true) => output.push_str("</>"),
+            ("a", false) => output.push_str("<underline>"),
+            ("a", true) => output.push_str("</>"),
+            ("li", false) => output.push_str("  - "),
+            ("li", true) => output.push('\n'),
+            ("ul", true) => output.push('\n'),
+            _ => {}
+        }
+    }
+    output.push_str(&escape_text(&article[last..]));
+
+    output.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Sonar Sweep ---</h2><p>For example:</p>
+<pre><code>199
+200
+</code></pre>
+<p>There are <em>7</em> measurements &gt; 0.</p></article>
+<p>Your puzzle answer was <code>1559</code>.</p>
+</main>"#;
+
+    #[test]
+    fn extract_articles() {
+        let description = Description::from_html(PAGE);
+        assert_eq!(description.articles.len(), 1);
+        assert!(description.part(Part::One).unwrap().contains("Sonar Sweep"));
+        assert!(description.part(Part::Two).is_none());
+
+        let cached = Description::from_html(&description.to_html());
+        assert_eq!(cached.articles, description.articles);
+    }
+
+    #[test]
+    fn render_markup() {
+        let description = Description::from_html(PAGE);
+        let rendered = render(description.part(Part::One).unwrap());
+
+        assert!(rendered.starts_with("<bold><bright-white>--- Day 1: Sonar Sweep ---</>"));
+        assert!(rendered.contains("<cyan>    199\n    200</>"));
+        assert!(rendered.contains("There are <bold><bright-white>7</> measurements"));
+        assert!(render_terminal(description.part(Part::One).unwrap()).contains("measurements > 0."));
+    }
+
+    #[test]
+    fn render_angle_brackets() {
+        let article = "<p>Some chunks: <code>&lt;([{}])&gt;</code> or <code>&lt;b&gt;</code></p>";
+
+        let rendered = render(article);
+        assert!(!rendered.contains("<([{"));
+        assert!(!rendered.contains("<b>"));
+
+        let terminal = render_terminal(article);
+        assert!(terminal.contains("<([{}])>"));
+        assert!(terminal.contains("<b>"));
+    }
+
+    #[test]
+    fn description_next_to_input() {
+        assert_eq!(
+            description_path(2021, 4),
+            Path::new("2021").join("4.description.html")
+        );
+    }
+}
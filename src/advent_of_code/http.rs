@@ -0,0 +1,269 @@
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::lazy::SyncLazy;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fs2::FileExt;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::{Method, StatusCode};
+
+use super::cache::Cache;
+use crate::config::Settings;
+use crate::prelude::*;
+
+/// HTTP client following the automation guidelines of the website
+///
+/// - identifies itself with a User-Agent including a contact address
+/// - spaces requests by a minimum interval, shared by every process through a lock file
+/// - retries transient failures of GET requests with an exponential backoff, or as long as the
+///   website asks with `Retry-After`
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: Client,
+    min_interval: Duration,
+    lock_file: Option<PathBuf>,
+    retries: u32,
+}
+
+static SHARED: SyncLazy<HttpClient> = SyncLazy::new(HttpClient::from_settings);
+
+/// Client configured in `puzzling.toml`, shared by the whole process
+pub fn shared() -> &'static HttpClient {
+    &SHARED
+}
+
+/// Default User-Agent, the website asks for a way to contact the author of automated requests
+pub fn user_agent(contact: Option<&str>) -> String {
+    format!(
+        "puzzling/{} (+https://github.com/Larandar/puzzling{})",
+        env!("CARGO_PKG_VERSION"),
+        contact.map(|c| format!("; {}", c)).unwrap_or_default()
+    )
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new(&user_agent(None), Duration::ZERO, None, 3)
+    }
+}
+
+impl HttpClient {
+    pub fn new(
+        user_agent: &str,
+        min_interval: Duration,
+        lock_file: Option<PathBuf>,
+        retries: u32,
+    ) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent(user_agent)
+                .build()
+                .expect("could not build HTTP client"),
+            min_interval,
+            lock_file,
+            retries,
+        }
+    }
+
+    pub fn from_settings() -> Self {
+        let settings = &Settings::get().advent_of_code;
+        if settings.contact.is_none() && settings.user_agent.is_none() {
+            warn!("No contact configured for the User-Agent, please set contact in puzzling.toml");
+        }
+
+        let agent = settings
+            .user_agent
+            .clone()
+            .unwrap_or_else(|| user_agent(settings.contact.as_deref()));
+        // Shared by every account, the limit is per person running the tool
+        let lock_file = Cache::base_dir().join("requests.lock");
+
+        Self::new(
+            &agent,
+            Duration::from_millis(settings.request_interval),
+            Some(lock_file),
+            settings.request_retries,
+        )
+    }
+
+    /// Underlying client, to build requests
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Send a request, politely
+    ///
+    /// Only GET requests are retried: a failed POST, such as an answer, may have been processed.
+    pub fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let request = request.build()?;
+        let retries = match *request.method() {
+            Method::GET => self.retries,
+            _ => 0,
+        };
+
+        let mut attempt = 0;
+        loop {
+            // Bodies are always buffered forms or JSON, so requests can be cloned
+            let this_attempt = request
+                .try_clone()
+                .expect("streaming requests cannot be retried");
+            if let Err(e) = self.throttle() {
+                warn!("Could not throttle request: {:?}", e);
+            }
+
+            let (transient, retry_after) = match self.client.execute(this_attempt) {
+                Ok(res)
+                    if res.status().is_server_error()
+                        || res.status() == StatusCode::TOO_MANY_REQUESTS =>
+                {
+                    if attempt >= retries {
+                        return Ok(res);
+                    }
+                    (format!("HTTP {}", res.status()), retry_after(&res))
+                }
+                Err(e) if (e.is_timeout() || e.is_connect()) && attempt < retries => {
+                    (e.to_string(), None)
+                }
+                result => return result,
+            };
+
+            attempt += 1;
+            let backoff = retry_after.unwrap_or_else(|| {
+                self.min_interval.max(Duration::from_millis(500)) * 2u32.pow(attempt)
+            });
+            warn!(
+                "Transient failure ({}), retrying in {}s",
+                transient,
+                backoff.as_secs_f32()
+            );
+            std::thread::sleep(backoff);
+        }
+    }
+
+    /// Wait until `min_interval` elapsed since the last request of any process
+    fn throttle(&self) -> Result<()> {
+        let path = match &self.lock_file {
+            Some(path) if !self.min_interval.is_zero() => path,
+            _ => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Creating lock file directory")?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path)
+            .context("Opening request lock file")?;
+        file.lock_exclusive().context("Locking request lock file")?;
+
+        // The lock file holds the time of the last request, in milliseconds
+        let mut last = String::new();
+        file.read_to_string(&mut last)?;
+        let last = Duration::from_millis(last.trim().parse().unwrap_or(0));
+        let elapsed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .saturating_sub(last);
+        if elapsed < self.min_interval {
+            std::thread::sleep(self.min_interval - elapsed);
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        write!(file, "{}", now)?;
+
+        file.unlock().context("Unlocking request lock file")
+    }
+}
+
+/// Delay asked by the website, in seconds (HTTP dates are not used by the website)
+fn retry_after(res: &Response) -> Option<Duration> {
+    res.headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::mock::Response as MockResponse;
+    use crate::advent_of_code::testing::{scratch, serve, serve_once, serve_responses};
+    use std::time::Instant;
+    use test_log::test;
+
+    #[test]
+    fn identify_with_user_agent() {
+        let (url, request) = serve_once(200, "");
+        let client = HttpClient::new(&user_agent(Some("me@example.com")), Duration::ZERO, None, 0);
+        client.send(client.client().get(url)).unwrap();
+
+        let request = request.join().unwrap().to_lowercase();
+        assert!(request.contains("user-agent: puzzling/"));
+        assert!(request.contains("; me@example.com)"));
+    }
+
+    #[test]
+    fn retry_transient_failures() {
+        let (url, requests) = serve(&[(503, ""), (200, "ok")]);
+        let client = HttpClient::new("test", Duration::ZERO, None, 1);
+
+        let res = client.send(client.client().get(url)).unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(requests.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn honour_retry_after() {
+        let (url, requests) = serve_responses(vec![
+            MockResponse::new(429, "").with_header("Retry-After", "2"),
+            MockResponse::new(200, "ok"),
+        ]);
+        let client = HttpClient::new("test", Duration::ZERO, None, 1);
+
+        let start = Instant::now();
+        let res = client.send(client.client().get(url)).unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        assert_eq!(requests.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn never_retry_posts() {
+        let (url, request) = serve_once(503, "");
+        let client = HttpClient::new("test", Duration::ZERO, None, 3);
+
+        let res = client
+            .send(client.client().post(url).form(&[("answer", "42")]))
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(request.join().unwrap().starts_with("POST"));
+    }
+
+    #[test]
+    fn give_up_after_retries() {
+        let (url, _) = serve_once(503, "");
+        let client = HttpClient::new("test", Duration::ZERO, None, 0);
+
+        let res = client.send(client.client().get(url)).unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn space_requests() {
+        let lock_file = scratch("space_requests.lock");
+        let client = HttpClient::new("test", Duration::from_millis(200), Some(lock_file), 0);
+
+        let start = Instant::now();
+        client.throttle().unwrap();
+        client.throttle().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
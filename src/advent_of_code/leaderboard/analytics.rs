@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use super::{days_in_event, Leaderboard, Member, StarCompletion};
+use crate::advent_of_code::challenge::unlock_time;
+use crate::advent_of_code::puzzle::Part;
+use crate::prelude::*;
+
+/// Times of a member on a day, counted from the puzzle unlock
+#[derive(Debug, Clone)]
+pub struct DaySplit<'a> {
+    pub member: &'a Member,
+    pub part_one: Option<Duration>,
+    pub part_two: Option<Duration>,
+}
+
+impl DaySplit<'_> {
+    /// Time spent on part two once part one was solved
+    pub fn delta(&self) -> Option<Duration> {
+        Some(self.part_two? - self.part_one?)
+    }
+}
+
+/// Ways of ranking the members of a leaderboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scoring {
+    /// Official local score: N points for the first member to get a star, N-1 for the second...
+    Local,
+    /// Most stars, then least time spent from unlock to each star
+    TotalTime,
+    /// Median position over all stars obtained
+    MedianRank,
+    /// Number of stars only
+    Stars,
+}
+
+impl FromStr for Scoring {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "local" => Ok(Scoring::Local),
+            "time" | "total-time" => Ok(Scoring::TotalTime),
+            "median" | "median-rank" => Ok(Scoring::MedianRank),
+            "stars" => Ok(Scoring::Stars),
+            _ => Err(anyhow!(
+                "unknown scoring: {} (expected local, time, median or stars)",
+                s
+            )),
+        }
+    }
+}
+
+/// Score of a member under a [Scoring] scheme
+#[derive(Debug, Clone, PartialEq)]
+pub enum Score {
+    Points(u64),
+    Time { stars: u32, total: Duration },
+    MedianRank(Option<f64>),
+    Stars(u32),
+}
+
+impl std::fmt::Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Score::Points(points) => write!(f, "{}", points),
+            Score::Time { stars, total } => {
+                write!(f, "{}★ {}", stars, format_duration(*total))
+            }
+            Score::MedianRank(Some(rank)) => write!(f, "{:.1}", rank),
+            Score::MedianRank(None) => write!(f, "-"),
+            Score::Stars(stars) => write!(f, "{}★", stars),
+        }
+    }
+}
+
+/// `HH:MM:SS`, prefixed by the number of days when needed
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if days > 0 {
+        format!("{}d {}", days, clock)
+    } else {
+        clock
+    }
+}
+
+fn star(member: &Member, day: u32, part: Part) -> Option<&StarCompletion> {
+    let completion = member.completion_day_level.get(&day)?;
+    match part {
+        Part::One => Some(&completion.part_one),
+        Part::Two => completion.part_two.as_ref(),
+    }
+}
+
+/// Time from unlock to a star
+fn solve_time(
+    leaderboard: &Leaderboard,
+    member: &Member,
+    day: u32,
+    part: Part,
+) -> Option<Duration> {
+    let unlock = unlock_time(leaderboard.year(), day as usize)
+        .ok()?
+        .timestamp() as u64;
+    star(member, day, part).map(|s| Duration::from_secs(s.get_star_ts.saturating_sub(unlock)))
+}
+
+/// Split times of every member who got at least a star on a day, fastest first
+pub fn day_splits<'a>(leaderboard: &'a Leaderboard, day: u32) -> Vec<DaySplit<'a>> {
+    leaderboard
+        .members
+        .values()
+        .map(|member| DaySplit {
+            member,
+            part_one: solve_time(leaderboard, member, day, Part::One),
+            part_two: solve_time(leaderboard, member, day, Part::Two),
+        })
+        .filter(|split| split.part_one.is_some())
+        .sorted_by_key(|split| (split.part_two.is_none(), split.part_two, split.part_one))
+        .collect()
+}
+
+/// Position (starting at 1) of each member for each star of the given days
+fn star_positions(
+    leaderboard: &Leaderboard,
+    days: &RangeInclusive<u32>,
+) -> HashMap<u64, Vec<usize>> {
+    let mut positions: HashMap<u64, Vec<usize>> = HashMap::new();
+    for day in days.clone() {
+        for part in [Part::One, Part::Two] {
+            leaderboard
+                .members
+                .values()
+                .filter_map(|m| star(m, day, part).map(|s| (s.get_star_ts, m.id)))
+                .sorted()
+                .enumerate()
+                .for_each(|(position, (_, id))| {
+                    positions.entry(id).or_default().push(position + 1)
+                });
+        }
+    }
+    positions
+}
+
+/// Rank the members over the given days, best first
+pub fn rank<'a>(
+    leaderboard: &'a Leaderboard,
+    scoring: Scoring,
+    days: Option<RangeInclusive<u32>>,
+) -> Vec<(&'a Member, Score)> {
+    let days = days.unwrap_or(1..=days_in_event(leaderboard.year()));
+    let positions = star_positions(leaderboard, &days);
+    let count = leaderboard.members.len();
+
+    let stars =
+        |member: &Member| -> u32 { days.clone().map(|day| member.stars_on(day) as u32).sum() };
+
+    let mut ranked = leaderboard
+        .members
+        .values()
+        .map(|member| {
+            let member_positions = positions.get(&member.id).cloned().unwrap_or_default();
+            let score = match scoring {
+                Scoring::Local => Score::Points(
+                    member_positions
+                        .iter()
+                        .map(|position| (count + 1 - position) as u64)
+                        .sum(),
+                ),
+                Scoring::TotalTime => Score::Time {
+                    stars: stars(member),
+                    total: days
+                        .clone()
+                        .flat_map(|day| [(day, Part::One), (day, Part::Two)])
+                        .filter_map(|(day, part)| solve_time(leaderboard, member, day, part))
+                        .sum(),
+                },
+                Scoring::MedianRank => Score::MedianRank(median(member_positions)),
+                Scoring::Stars => Score::Stars(stars(member)),
+            };
+            (member, score)
+        })
+        .collect_vec();
+
+    ranked.sort_by(|(a, a_score), (b, b_score)| {
+        let by_score = match (a_score, b_score) {
+            (Score::Points(a), Score::Points(b)) => b.cmp(a),
+            (Score::Stars(a), Score::Stars(b)) => b.cmp(a),
+            (
+                Score::Time {
+                    stars: a_stars,
+                    total: a_total,
+                },
+                Score::Time {
+                    stars: b_stars,
+                    total: b_total,
+                },
+            ) => b_stars.cmp(a_stars).then(a_total.cmp(b_total)),
+            (Score::MedianRank(a), Score::MedianRank(b)) => match (a, b) {
+                (Some(a), Some(b)) => a.total_cmp(b),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            },
+            _ => unreachable!("all members are ranked with the same scoring"),
+        };
+        by_score.then(a.last_star_ts.cmp(&b.last_star_ts))
+    });
+    ranked
+}
+
+fn median(mut values: Vec<usize>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let middle = values.len() / 2;
+    Some(if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) as f64 / 2.
+    } else {
+        values[middle] as f64
+    })
+}
+
+/// Table of the split times of a day
+pub fn render_splits(splits: &[DaySplit<'_>]) -> String {
+    let time = |d: Option<Duration>| d.map_or("-".into(), format_duration);
+
+    let mut lines = vec![format!(
+        "{:>4} {:>12} {:>12} {:>12} {}",
+        "", "Part 1", "Part 2", "Delta", "Member"
+    )];
+    for (rank, split) in splits.iter().enumerate() {
+        lines.push(format!(
+            "{:>3}) {:>12} {:>12} {:>12} {}",
+            rank + 1,
+            time(split.part_one),
+            time(split.part_two),
+            time(split.delta()),
+            split.member.display_name()
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Table of a ranking
+pub fn render_ranking(ranked: &[(&Member, Score)]) -> String {
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(rank, (member, score))| {
+            format!("{:>3}) {:>16} {}", rank + 1, score, member.display_name())
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    /// Day 1 of 2021 unlocked at 1638334800
+    const LEADERBOARD: &str = r#"{
+        "event": "2021",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 2, "local_score": 5, "last_star_ts": 1638335400,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1638334900}, "2": {"get_star_ts": 1638335400}}
+                }
+            },
+            "2": {
+                "id": 2, "name": "bob", "stars": 2, "local_score": 4, "last_star_ts": 1638335100,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1638335000}, "2": {"get_star_ts": 1638335100}}
+                }
+            }
+        }
+    }"#;
+
+    fn leaderboard() -> Leaderboard {
+        serde_json::from_str(LEADERBOARD).unwrap()
+    }
+
+    #[test]
+    fn compute_splits() {
+        let leaderboard = leaderboard();
+        let splits = day_splits(&leaderboard, 1);
+
+        assert_eq!(splits[0].member.display_name(), "bob");
+        assert_eq!(splits[0].part_one, Some(Duration::from_secs(200)));
+        assert_eq!(splits[0].delta(), Some(Duration::from_secs(100)));
+        assert_eq!(splits[1].delta(), Some(Duration::from_secs(500)));
+    }
+
+    #[test]
+    fn rank_by_scheme() {
+        let leaderboard = leaderboard();
+        let names = |scoring| {
+            rank(&leaderboard, scoring, None)
+                .iter()
+                .map(|(m, _)| m.display_name())
+                .collect_vec()
+        };
+
+        assert_eq!(names(Scoring::Local), ["bob", "alice"]);
+        assert_eq!(names(Scoring::TotalTime), ["bob", "alice"]);
+        assert_eq!(names(Scoring::MedianRank), ["bob", "alice"]);
+        assert_eq!(names(Scoring::Stars), ["bob", "alice"]);
+
+        let local = rank(&leaderboard, Scoring::Local, None);
+        assert_eq!(local[0].1, Score::Points(3));
+        assert_eq!(local[1].1, Score::Points(3));
+    }
+}
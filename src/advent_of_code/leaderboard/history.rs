@@ -0,0 +1,308 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{TimeZone, Utc};
+use serde_derive::Serialize;
+
+use super::Leaderboard;
+use crate::advent_of_code::cache::Cache;
+use crate::prelude::*;
+
+/// A leaderboard as it was at a given time
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Unix timestamp of the fetch
+    pub taken_at: u64,
+    pub leaderboard: Leaderboard,
+}
+
+/// Standing of a member in a snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryPoint {
+    pub taken_at: u64,
+    pub member_id: u64,
+    pub name: String,
+    pub rank: usize,
+    pub local_score: u64,
+    pub stars: u32,
+}
+
+/// Directory of the snapshots of a leaderboard, relative to the cache root
+fn snapshots_directory(year: usize, id: u64) -> PathBuf {
+    Path::new(&year.to_string())
+        .join("leaderboards")
+        .join(id.to_string())
+}
+
+/// Keep a copy of a freshly fetched leaderboard
+pub fn record_snapshot(cache: &Cache, id: u64, snapshot: &Snapshot) -> Result<()> {
+    let path = snapshots_directory(snapshot.leaderboard.year(), id)
+        .join(snapshot.taken_at.to_string())
+        .with_extension("json");
+    let contents =
+        serde_json::to_string(&snapshot.leaderboard).context("Serializing leaderboard snapshot")?;
+    cache.write(path, &contents)
+}
+
+/// Every snapshot recorded for a leaderboard, oldest first
+pub fn snapshots(cache: &Cache, year: usize, id: u64) -> Result<Vec<Snapshot>> {
+    let directory = cache.root().join(snapshots_directory(year, id));
+    if !directory.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut snapshots = vec![];
+    for entry in std::fs::read_dir(directory).context("Listing leaderboard snapshots")? {
+        let path = entry?.path();
+        let taken_at = match path.file_stem().and_then(|s| s.to_str()?.parse().ok()) {
+            Some(taken_at) => taken_at,
+            None => continue,
+        };
+        let contents = std::fs::read_to_string(&path).context("Reading leaderboard snapshot")?;
+        snapshots.push(Snapshot {
+            taken_at,
+            leaderboard: serde_json::from_str(&contents)
+                .with_context(|| format!("Deserializing leaderboard snapshot {:?}", path))?,
+        });
+    }
+    snapshots.sort_by_key(|s| s.taken_at);
+    Ok(snapshots)
+}
+
+/// Flatten snapshots into the standing of each member over time
+pub fn history(snapshots: &[Snapshot]) -> Vec<HistoryPoint> {
+    snapshots
+        .iter()
+        .flat_map(|snapshot| {
+            snapshot
+                .leaderboard
+                .ranked()
+                .into_iter()
+                .enumerate()
+                .map(|(rank, member)| HistoryPoint {
+                    taken_at: snapshot.taken_at,
+                    member_id: member.id,
+                    name: member.display_name(),
+                    rank: rank + 1,
+                    local_score: member.local_score,
+                    stars: member.stars,
+                })
+                .collect_vec()
+        })
+        .collect()
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    Utc.timestamp_opt(timestamp as i64, 0).single().map_or_else(
+        || timestamp.to_string(),
+        |t| t.format("%Y-%m-%d %H:%M").to_string(),
+    )
+}
+
+/// One line per member and per day, with the last standing of the day
+pub fn to_text(points: &[HistoryPoint]) -> String {
+    points
+        .iter()
+        .into_group_map_by(|p| p.member_id)
+        .into_values()
+        .sorted_by_key(|member_points| member_points.last().map(|p| p.rank))
+        .map(|member_points| {
+            let progression = member_points
+                .iter()
+                .chunk_by(|p| p.taken_at / 86400)
+                .into_iter()
+                .filter_map(|(_, day_points)| day_points.last())
+                .map(|p| {
+                    format!(
+                        "{} #{} {}pts",
+                        &format_timestamp(p.taken_at)[5..10],
+                        p.rank,
+                        p.local_score
+                    )
+                })
+                .join(" → ");
+            format!("{}: {}", member_points[0].name, progression)
+        })
+        .join("\n")
+}
+
+pub fn to_csv(points: &[HistoryPoint]) -> String {
+    let escape = |value: &str| format!("\"{}\"", value.replace('"', "\"\""));
+    std::iter::once("taken_at,member_id,name,rank,local_score,stars".to_string())
+        .chain(points.iter().map(|p| {
+            format!(
+                "{},{},{},{},{},{}",
+                format_timestamp(p.taken_at),
+                p.member_id,
+                escape(&p.name),
+                p.rank,
+                p.local_score,
+                p.stars
+            )
+        }))
+        .join("\n")
+}
+
+pub fn to_json(points: &[HistoryPoint]) -> Result<String> {
+    serde_json::to_string_pretty(points).context("Serializing leaderboard history")
+}
+
+/// Line chart of the score of each member over time
+pub fn to_svg(points: &[HistoryPoint]) -> String {
+    const WIDTH: f64 = 800.;
+    const HEIGHT: f64 = 400.;
+    const MARGIN: f64 = 40.;
+    const LEGEND: f64 = 200.;
+    const COLORS: [&str; 10] = [
+        "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+        "#bcbd22", "#17becf",
+    ];
+
+    let (start, end) = points
+        .iter()
+        .map(|p| p.taken_at)
+        .minmax()
+        .into_option()
+        .unwrap_or((0, 0));
+    let max_score = points
+        .iter()
+        .map(|p| p.local_score)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let x = |taken_at: u64| {
+        MARGIN + (taken_at - start) as f64 / (end - start).max(1) as f64 * (WIDTH - 2. * MARGIN)
+    };
+    let y = |score: u64| HEIGHT - MARGIN - score as f64 / max_score as f64 * (HEIGHT - 2. * MARGIN);
+
+    let mut svg = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="12">"#,
+            WIDTH + LEGEND,
+            HEIGHT
+        ),
+        format!(
+            r##"<rect width="100%" height="100%" fill="#0f0f23"/><g stroke="#cccccc"><line x1="{m}" y1="{b}" x2="{r}" y2="{b}"/><line x1="{m}" y1="{m}" x2="{m}" y2="{b}"/></g>"##,
+            m = MARGIN,
+            b = HEIGHT - MARGIN,
+            r = WIDTH - MARGIN
+        ),
+        format!(
+            r##"<g fill="#cccccc"><text x="{}" y="{}">{}</text><text x="{}" y="{}" text-anchor="end">{}</text><text x="{}" y="{}" text-anchor="end">{}</text></g>"##,
+            MARGIN,
+            HEIGHT - MARGIN / 3.,
+            format_timestamp(start),
+            WIDTH - MARGIN,
+            HEIGHT - MARGIN / 3.,
+            format_timestamp(end),
+            MARGIN - 4.,
+            MARGIN,
+            max_score
+        ),
+    ];
+
+    let members = points
+        .iter()
+        .into_group_map_by(|p| p.member_id)
+        .into_values()
+        .sorted_by_key(|member_points| member_points.last().map(|p| p.rank))
+        .collect_vec();
+    for (index, member_points) in members.iter().enumerate() {
+        let color = COLORS[index % COLORS.len()];
+        let line = member_points
+            .iter()
+            .map(|p| format!("{:.1},{:.1}", x(p.taken_at), y(p.local_score)))
+            .join(" ");
+        let name = member_points[0]
+            .name
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        svg.push(format!(
+            r#"<polyline fill="none" stroke="{}" stroke-width="2" points="{}"/>"#,
+            color, line
+        ));
+        svg.push(format!(
+            r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+            WIDTH,
+            MARGIN + 16. * index as f64,
+            color,
+            name
+        ));
+    }
+    svg.push("</svg>".into());
+    svg.join("\n")
+}
+
+/// Standalone page embedding the [to_svg] chart
+pub fn to_html(points: &[HistoryPoint], title: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>{title}</title></head>
+<body style="background: #0f0f23; color: #cccccc; font-family: monospace">
+<h1>{title}</h1>
+{svg}
+</body>
+</html>
+"#,
+        title = title,
+        svg = to_svg(points)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::testing::scratch;
+    use test_log::test;
+
+    fn snapshot(taken_at: u64, alice: u64, bob: u64) -> Snapshot {
+        Snapshot {
+            taken_at,
+            leaderboard: serde_json::from_str(&format!(
+                r#"{{"event": "2021", "owner_id": 1, "members": {{
+                    "1": {{"id": 1, "name": "alice", "stars": 1, "local_score": {}, "last_star_ts": 1}},
+                    "2": {{"id": 2, "name": "bob, \"the builder\"", "stars": 1, "local_score": {}, "last_star_ts": 2}}
+                }}}}"#,
+                alice, bob
+            ))
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn store_and_reload_snapshots() {
+        let cache = Cache::new(scratch("store_and_reload_snapshots"));
+
+        record_snapshot(&cache, 42, &snapshot(1638400000, 2, 1)).unwrap();
+        record_snapshot(&cache, 42, &snapshot(1638300000, 1, 2)).unwrap();
+
+        let snapshots = snapshots(&cache, 2021, 42).unwrap();
+        assert_eq!(
+            snapshots.iter().map(|s| s.taken_at).collect_vec(),
+            [1638300000, 1638400000]
+        );
+    }
+
+    #[test]
+    fn export_history() {
+        let points = history(&[snapshot(1638300000, 1, 2), snapshot(1638400000, 4, 3)]);
+        assert_eq!(points.len(), 4);
+        assert_eq!(
+            (points[0].name.as_str(), points[0].rank),
+            ("bob, \"the builder\"", 1)
+        );
+        assert_eq!((points[2].name.as_str(), points[2].rank), ("alice", 1));
+
+        let csv = to_csv(&points);
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains(r#","bob, ""the builder""","#));
+
+        let svg = to_svg(&points);
+        assert_eq!(svg.matches("<polyline").count(), 2);
+
+        let text = to_text(&points);
+        assert!(text.starts_with("alice: 11-30 #2 1pts → 12-01 #1 4pts"));
+    }
+}
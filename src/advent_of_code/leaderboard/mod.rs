@@ -0,0 +1,289 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use chrono::{Datelike, Utc};
+use reqwest::StatusCode;
+use serde::{Deserialize as _, Deserializer};
+use serde_derive::{Deserialize, Serialize};
+
+use super::cache::Cache;
+use super::calendar::star_glyph;
+use super::session::Session;
+use crate::config::Settings;
+use crate::prelude::*;
+
+pub mod analytics;
+pub mod history;
+pub mod watch;
+
+/// The website asks not to fetch a leaderboard more than once every 15 minutes
+pub const MINIMUM_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard, as served by `/YEAR/leaderboard/private/view/ID.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    #[serde(deserialize_with = "number_or_string")]
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+/// A participant of a private leaderboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Member {
+    #[serde(deserialize_with = "number_or_string")]
+    pub id: u64,
+    /// Anonymous users have no name
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    #[serde(default)]
+    pub global_score: u64,
+    #[serde(deserialize_with = "number_or_string")]
+    pub last_star_ts: u64,
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, DayCompletion>,
+}
+
+/// Stars obtained by a member on a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayCompletion {
+    #[serde(rename = "1")]
+    pub part_one: StarCompletion,
+    #[serde(rename = "2")]
+    pub part_two: Option<StarCompletion>,
+}
+
+/// When a star was obtained
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarCompletion {
+    #[serde(deserialize_with = "number_or_string")]
+    pub get_star_ts: u64,
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+/// Older events serialize numbers as strings
+fn number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(serde_derive::Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u64),
+        String(String),
+    }
+
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(n) => Ok(n),
+        NumberOrString::String(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+impl Member {
+    /// Name displayed by the website
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Number of stars obtained on a day
+    pub fn stars_on(&self, day: u32) -> u8 {
+        match self.completion_day_level.get(&day) {
+            Some(DayCompletion {
+                part_two: Some(_), ..
+            }) => 2,
+            Some(_) => 1,
+            None => 0,
+        }
+    }
+}
+
+impl Leaderboard {
+    pub fn year(&self) -> usize {
+        self.event.parse().unwrap_or_default()
+    }
+
+    /// Members ordered like on the website: by local score, then by who got there first
+    pub fn ranked(&self) -> Vec<&Member> {
+        self.members
+            .values()
+            .sorted_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id))
+            .collect()
+    }
+}
+
+/// Number of puzzles of an event
+pub fn days_in_event(year: usize) -> u32 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// Year of the current, or last, event
+pub fn current_event() -> usize {
+    let now = Utc::now();
+    if now.month() == 12 {
+        now.year() as usize
+    } else {
+        now.year() as usize - 1
+    }
+}
+
+/// Time to wait between two fetches of the same leaderboard
+pub fn refresh_interval() -> Duration {
+    Duration::from_secs(Settings::get().advent_of_code.cache_time).max(MINIMUM_REFRESH)
+}
+
+fn cache_key(year: usize, id: u64) -> String {
+    format!("leaderboards/{}/{}.json", year, id)
+}
+
+/// Private leaderboard, from the cache if fetched less than 15 minutes ago
+pub fn leaderboard(year: usize, id: u64) -> Result<Leaderboard> {
+    let cache = Cache::from_settings();
+    if let Ok(contents) = cache.read_volatile(&cache_key(year, id), refresh_interval()) {
+        return serde_json::from_str(&contents).context("Deserializing cached leaderboard");
+    }
+
+    refresh_leaderboard(&cache, &Session::from_settings()?, year, id)
+}
+
+/// Fetch a leaderboard, then cache it and record it as a [history::Snapshot]
+pub fn refresh_leaderboard(
+    cache: &Cache,
+    session: &Session,
+    year: usize,
+    id: u64,
+) -> Result<Leaderboard> {
+    let leaderboard = fetch_leaderboard(session, year, id)?;
+    if let Err(e) =
+        cache.write_volatile(&cache_key(year, id), &serde_json::to_string(&leaderboard)?)
+    {
+        warn!("Could not save leaderboard to cache: {:?}", e);
+    }
+
+    let snapshot = history::Snapshot {
+        taken_at: Utc::now().timestamp() as u64,
+        leaderboard,
+    };
+    if let Err(e) = history::record_snapshot(&cache, id, &snapshot) {
+        warn!("Could not record leaderboard snapshot: {:?}", e);
+    }
+    Ok(snapshot.leaderboard)
+}
+
+pub fn fetch_leaderboard(session: &Session, year: usize, id: u64) -> Result<Leaderboard> {
+    info!("Fetching leaderboard {} of {}", id, year);
+    let res = session
+        .get(&format!("{}/leaderboard/private/view/{}.json", year, id))
+        .context("Failed to send request")?;
+
+    match res.status() {
+        StatusCode::OK => {
+            let body = res.text().context("Failed to read response body")?;
+            // Unauthorized requests are redirected to an HTML page
+            if body.trim_start().starts_with('<') {
+                return Err(anyhow!(
+                    "Invalid session or no access to leaderboard {}",
+                    id
+                ));
+            }
+            serde_json::from_str(&body).context("Deserializing leaderboard")
+        }
+        StatusCode::NOT_FOUND => Err(anyhow!("Leaderboard {} not found", id)),
+        unhandled => match res.error_for_status() {
+            Ok(_) => Err(anyhow!("Expected to fail for status: {:?}", unhandled)),
+            Err(e) => Err(e).with_context(|| format!("Unhandled HTTP error: {:?}", unhandled)),
+        },
+    }
+}
+
+/// Star glyphs of a member for every day of the event, in paris markup
+pub fn star_glyphs(member: &Member, days: u32) -> String {
+    (1..=days)
+        .map(|day| star_glyph(member.stars_on(day)))
+        .collect()
+}
+
+/// Ranked table of the members, in paris markup
+pub fn render_table(leaderboard: &Leaderboard) -> String {
+    let days = days_in_event(leaderboard.year());
+    // Day numbers are written vertically above the stars
+    let tens = (1..=days)
+        .map(|day| {
+            if day < 10 {
+                " ".into()
+            } else {
+                (day / 10).to_string()
+            }
+        })
+        .join("");
+    let units = (1..=days).map(|day| (day % 10).to_string()).join("");
+
+    let mut lines = vec![
+        format!("{:>4} {:>5} {}", "", "", tens),
+        format!("{:>4} {:>5} {}", "", "", units),
+    ];
+    for (rank, member) in leaderboard.ranked().iter().enumerate() {
+        lines.push(format!(
+            "{:>3}) {:>5} {} {}",
+            rank + 1,
+            member.local_score,
+            star_glyphs(member, days),
+            member.display_name()
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2021",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1638400000,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1638336000, "star_index": 1}, "2": {"get_star_ts": 1638336600, "star_index": 2}},
+                    "2": {"1": {"get_star_ts": 1638400000, "star_index": 3}}
+                }
+            },
+            "2": {
+                "id": "2", "name": null, "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": "0", "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn deserialize_leaderboard() {
+        let leaderboard: Leaderboard = serde_json::from_str(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.year(), 2021);
+
+        let ranked = leaderboard.ranked();
+        assert_eq!(ranked[0].display_name(), "alice");
+        assert_eq!(ranked[0].stars_on(1), 2);
+        assert_eq!(ranked[0].stars_on(2), 1);
+        assert_eq!(ranked[0].stars_on(3), 0);
+        assert_eq!(ranked[1].display_name(), "(anonymous user #2)");
+    }
+
+    #[test]
+    fn render_ranked_table() {
+        let leaderboard: Leaderboard = serde_json::from_str(LEADERBOARD).unwrap();
+        let table = render_table(&leaderboard);
+        let lines = table.lines().collect_vec();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].ends_with("1234567890123456789012345"));
+        assert!(lines[2].starts_with("  1)    10 <bright-yellow>★</><white>★</><dimmed>·</>"));
+        assert!(lines[2].ends_with("alice"));
+    }
+}
@@ -0,0 +1,274 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde_derive::Serialize;
+
+use super::{refresh_interval, refresh_leaderboard, Leaderboard};
+use crate::advent_of_code::cache::Cache;
+use crate::advent_of_code::http::HttpClient;
+use crate::advent_of_code::session::Session;
+use crate::prelude::*;
+
+/// Something that happened between two fetches of a leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Event {
+    Joined {
+        member: String,
+    },
+    Star {
+        member: String,
+        day: u32,
+        /// Stars of the member on that day, after the change
+        stars: u8,
+        rank_before: usize,
+        rank_after: usize,
+    },
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Joined { member } => write!(f, "{} joined the leaderboard", member),
+            Event::Star {
+                member,
+                day,
+                stars,
+                rank_before,
+                rank_after,
+            } => {
+                write!(
+                    f,
+                    "{} got {} on day {} ",
+                    member,
+                    "★".repeat(*stars as usize),
+                    day
+                )?;
+                if rank_before == rank_after {
+                    write!(f, "(rank {})", rank_after)
+                } else {
+                    write!(f, "(rank {} → {})", rank_before, rank_after)
+                }
+            }
+        }
+    }
+}
+
+/// Changes between two states of a leaderboard
+pub fn diff(before: &Leaderboard, after: &Leaderboard) -> Vec<Event> {
+    let rank_of = |leaderboard: &Leaderboard, id: u64| {
+        leaderboard
+            .ranked()
+            .iter()
+            .position(|m| m.id == id)
+            .map_or(0, |position| position + 1)
+    };
+
+    let mut events = vec![];
+    for member in after.ranked() {
+        let previous = before.members.values().find(|m| m.id == member.id);
+        let previous = match previous {
+            Some(previous) => previous,
+            None => {
+                events.push(Event::Joined {
+                    member: member.display_name(),
+                });
+                continue;
+            }
+        };
+
+        for (day, _) in member.completion_day_level.iter() {
+            let stars = member.stars_on(*day);
+            if stars > previous.stars_on(*day) {
+                events.push(Event::Star {
+                    member: member.display_name(),
+                    day: *day,
+                    stars,
+                    rank_before: rank_of(before, member.id),
+                    rank_after: rank_of(after, member.id),
+                });
+            }
+        }
+    }
+    events
+}
+
+/// Destination of leaderboard events
+pub trait EventSink {
+    fn send(&mut self, event: &Event) -> Result<()>;
+}
+
+/// Print events in the terminal
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink;
+
+impl EventSink for StdoutSink {
+    fn send(&mut self, event: &Event) -> Result<()> {
+        println!("{}", event);
+        Ok(())
+    }
+}
+
+/// Append events to a file, one JSON object per line
+#[derive(Debug, Clone)]
+pub struct JsonLinesSink {
+    pub path: PathBuf,
+}
+
+impl EventSink for JsonLinesSink {
+    fn send(&mut self, event: &Event) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("Opening events file")?;
+        writeln!(file, "{}", serde_json::to_string(event)?).context("Writing event")
+    }
+}
+
+/// POST events as JSON, with a human readable `message`, to a webhook
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+    pub url: String,
+}
+
+impl EventSink for WebhookSink {
+    fn send(&mut self, event: &Event) -> Result<()> {
+        let mut payload = serde_json::to_value(event)?;
+        payload["message"] = event.to_string().into();
+
+        let http = HttpClient::default();
+        http.send(http.client().post(&self.url).json(&payload))
+            .context("Failed to send event to webhook")?
+            .error_for_status()
+            .context("Webhook refused event")?;
+        Ok(())
+    }
+}
+
+/// Build a sink from its textual description: `stdout`, `jsonl:PATH` or `webhook:URL`
+pub fn sink_from_spec(spec: &str) -> Result<Box<dyn EventSink>> {
+    match spec.split_once(':') {
+        None if spec == "stdout" => Ok(Box::new(StdoutSink)),
+        Some(("jsonl", path)) => Ok(Box::new(JsonLinesSink { path: path.into() })),
+        Some(("webhook", url)) => Ok(Box::new(WebhookSink { url: url.into() })),
+        _ => Err(anyhow!(
+            "unknown sink: {} (expected stdout, jsonl:PATH or webhook:URL)",
+            spec
+        )),
+    }
+}
+
+/// Poll a leaderboard forever, at the allowed interval, and dispatch the changes to the sinks
+pub fn watch(year: usize, id: u64, sinks: &mut [Box<dyn EventSink>]) -> Result<!> {
+    let cache = Cache::from_settings();
+    let session = Session::from_settings()?;
+
+    let mut previous = super::leaderboard(year, id)?;
+    loop {
+        info!("Next refresh in {}s", refresh_interval().as_secs());
+        std::thread::sleep(refresh_interval());
+
+        let current = match refresh_leaderboard(&cache, &session, year, id) {
+            Ok(current) => current,
+            Err(e) => {
+                warn!("Could not refresh leaderboard: {:?}", e);
+                continue;
+            }
+        };
+
+        for event in diff(&previous, &current) {
+            for sink in sinks.iter_mut() {
+                if let Err(e) = sink.send(&event) {
+                    warn!("Could not dispatch event: {:?}", e);
+                }
+            }
+        }
+        previous = current;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::testing::{scratch, serve_once};
+    use test_log::test;
+
+    fn leaderboard(bob_day_two: &str) -> Leaderboard {
+        serde_json::from_str(&format!(
+            r#"{{"event": "2021", "owner_id": 1, "members": {{
+                "1": {{"id": 1, "name": "alice", "stars": 2, "local_score": 4, "last_star_ts": 10,
+                    "completion_day_level": {{"1": {{"1": {{"get_star_ts": 5}}, "2": {{"get_star_ts": 10}}}}}}}},
+                "2": {{"id": 2, "name": "bob", "stars": 1, "local_score": 3, "last_star_ts": 20,
+                    "completion_day_level": {{"1": {{"1": {{"get_star_ts": 20}}}}{}}}}}
+            }}}}"#,
+            bob_day_two
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn diff_leaderboards() {
+        let before = leaderboard("");
+        let after = leaderboard(r#", "2": {"1": {"get_star_ts": 30}, "2": {"get_star_ts": 40}}"#);
+        // Scores are not recomputed, rank is the same
+        assert_eq!(
+            diff(&before, &after),
+            [Event::Star {
+                member: "bob".into(),
+                day: 2,
+                stars: 2,
+                rank_before: 2,
+                rank_after: 2
+            }]
+        );
+        assert_eq!(
+            diff(&before, &after)[0].to_string(),
+            "bob got ★★ on day 2 (rank 2)"
+        );
+    }
+
+    #[test]
+    fn event_message() {
+        let event = Event::Star {
+            member: "alice".into(),
+            day: 12,
+            stars: 2,
+            rank_before: 3,
+            rank_after: 1,
+        };
+        assert_eq!(event.to_string(), "alice got ★★ on day 12 (rank 3 → 1)");
+    }
+
+    #[test]
+    fn send_to_webhook() {
+        let (url, request) = serve_once(204, "");
+        let mut sink = sink_from_spec(&format!("webhook:{}/hook", url)).unwrap();
+
+        sink.send(&Event::Joined {
+            member: "carol".into(),
+        })
+        .unwrap();
+
+        let request = request.join().unwrap();
+        assert!(request.starts_with("POST /hook "));
+        assert!(request.contains(r#""kind":"joined""#));
+        assert!(request.contains(r#""message":"carol joined the leaderboard""#));
+    }
+
+    #[test]
+    fn refresh_from_local_server() {
+        let (url, _) = serve_once(200, &serde_json::to_string(&leaderboard("")).unwrap());
+        let cache = Cache::new(scratch("refresh_from_local_server"));
+
+        let current = refresh_leaderboard(&cache, &Session::new(url, "secret"), 2021, 42).unwrap();
+        assert!(diff(&leaderboard(""), &current).is_empty());
+        assert_eq!(
+            super::super::history::snapshots(&cache, 2021, 42)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_derive::{Deserialize, Serialize};
+
+use super::cache::Cache;
+use super::puzzle::Part;
+use super::submit::Verdict;
+use crate::prelude::*;
+
+/// A single answer submitted to the website
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Guess {
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix timestamp of the submission
+    pub submitted_at: u64,
+}
+
+/// History of the answers submitted for a day, persisted next to the challenge cache
+#[derive(Debug, Clone, Default)]
+pub struct AnswerLedger {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl AnswerLedger {
+    /// Location of the ledger of a day
+    pub fn path(year: usize, day: usize) -> PathBuf {
+        Self::path_in(&Cache::from_settings(), year, day)
+    }
+
+    /// Location of the ledger of a day in a given cache, e.g. of another profile
+    pub fn path_in(cache: &Cache, year: usize, day: usize) -> PathBuf {
+        cache
+            .root()
+            .join(year.to_string())
+            .join(format!("{}.answers.json", day))
+    }
+
+    /// Open the ledger of a day, an empty ledger is returned if none was saved yet
+    pub fn open(year: usize, day: usize) -> Result<Self> {
+        Self::open_at(Self::path(year, day))
+    }
+
+    /// Open a ledger stored at an arbitrary location
+    pub fn open_at(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let guesses = if path.exists() {
+            let contents = std::fs::read_to_string(&path).context("Reading answer ledger")?;
+            serde_json::from_str(&contents).context("Deserializing answer ledger")?
+        } else {
+            vec![]
+        };
+        Ok(Self { path, guesses })
+    }
+
+    /// Every ledger saved in the cache, ordered by year and day
+    pub fn all() -> Result<Vec<(usize, usize, Self)>> {
+        Self::all_in(&Cache::from_settings())
+    }
+
+    /// Every ledger saved in a given cache, ordered by year and day
+    pub fn all_in(cache: &Cache) -> Result<Vec<(usize, usize, Self)>> {
+        let root = cache.root();
+        if !root.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut ledgers = vec![];
+        for year in std::fs::read_dir(root).context("Listing cache directory")? {
+            let year = year?.path();
+            let year_number = match year.file_name().and_then(|n| n.to_str()?.parse().ok()) {
+                Some(year_number) => year_number,
+                None => continue,
+            };
+            for file in std::fs::read_dir(&year).context("Listing cache year directory")? {
+                let file = file?.path();
+                let day = file
+                    .file_name()
+                    .and_then(|n| n.to_str()?.strip_suffix(".answers.json")?.parse().ok());
+                if let Some(day) = day {
+                    ledgers.push((year_number, day, Self::open_at(&file)?));
+                }
+            }
+        }
+        ledgers.sort_by_key(|(year, day, _)| (*year, *day));
+        Ok(ledgers)
+    }
+
+    /// All the guesses for a part, in submission order
+    pub fn guesses(&self, part: Part) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |g| g.part == part)
+    }
+
+    /// The accepted answer of a part, if any
+    pub fn solution(&self, part: Part) -> Option<&str> {
+        self.guesses(part)
+            .find(|g| g.verdict == Verdict::Correct)
+            .map(|g| g.answer.as_str())
+    }
+
+    /// Exclusive numeric bounds learned from the "too low" and "too high" hints
+    pub fn bounds(&self, part: Part) -> (Option<i128>, Option<i128>) {
+        let hinted = |verdict: Verdict| {
+            self.guesses(part)
+                .filter(move |g| g.verdict == verdict)
+                .filter_map(|g| g.answer.parse::<i128>().ok())
+        };
+        (
+            hinted(Verdict::TooLow).max(),
+            hinted(Verdict::TooHigh).min(),
+        )
+    }
+
+    /// Refuse answers that would certainly be rejected by the website
+    pub fn check(&self, part: Part, answer: &str) -> Result<()> {
+        let answer = answer.trim();
+
+        if let Some(solution) = self.solution(part) {
+            return Err(anyhow!("Part {} already solved with: {}", part, solution));
+        }
+
+        if let Some(guess) = self.guesses(part).find(|g| g.answer == answer) {
+            return Err(anyhow!(
+                "Answer {} already rejected: {}",
+                answer,
+                guess.verdict
+            ));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            match self.bounds(part) {
+                (Some(low), _) if value <= low => {
+                    return Err(anyhow!(
+                        "Answer {} is too low, must be above {}",
+                        value,
+                        low
+                    ))
+                }
+                (_, Some(high)) if value >= high => {
+                    return Err(anyhow!(
+                        "Answer {} is too high, must be below {}",
+                        value,
+                        high
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the verdict of a submission and persist the ledger
+    ///
+    /// Only judged answers are kept, rate limited submissions did not teach us anything.
+    pub fn record(&mut self, part: Part, answer: &str, verdict: Verdict) -> Result<()> {
+        if !verdict.is_judged() {
+            return Ok(());
+        }
+
+        self.guesses.push(Guess {
+            part,
+            answer: answer.trim().to_string(),
+            verdict,
+            submitted_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        });
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).context("Creating answer ledger directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(&self.guesses).context("Serializing answer ledger")?;
+        std::fs::write(&self.path, contents).context("Writing answer ledger")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::testing::scratch;
+    use test_log::test;
+
+    fn ledger(name: &str) -> AnswerLedger {
+        AnswerLedger::open_at(scratch(&format!("{}.json", name))).unwrap()
+    }
+
+    #[test]
+    fn refuse_rejected_answer() {
+        let mut ledger = ledger("refuse_rejected_answer");
+        ledger.record(Part::One, "abc", Verdict::Wrong).unwrap();
+
+        assert!(ledger.check(Part::One, "abc").is_err());
+        assert!(ledger.check(Part::One, "abd").is_ok());
+        assert!(ledger.check(Part::Two, "abc").is_ok());
+    }
+
+    #[test]
+    fn refuse_out_of_bounds_answer() {
+        let mut ledger = ledger("refuse_out_of_bounds_answer");
+        ledger.record(Part::One, "100", Verdict::TooLow).unwrap();
+        ledger.record(Part::One, "200", Verdict::TooHigh).unwrap();
+
+        assert_eq!(ledger.bounds(Part::One), (Some(100), Some(200)));
+        assert!(ledger.check(Part::One, "42").is_err());
+        assert!(ledger.check(Part::One, "250").is_err());
+        assert!(ledger.check(Part::One, "150").is_ok());
+    }
+
+    #[test]
+    fn refuse_solved_part() {
+        let mut ledger = ledger("refuse_solved_part");
+        ledger
+            .record(Part::Two, "1", Verdict::RateLimited(Default::default()))
+            .unwrap();
+        assert_eq!(ledger.guesses(Part::Two).count(), 0);
+
+        ledger.record(Part::Two, "150", Verdict::Correct).unwrap();
+        assert_eq!(ledger.solution(Part::Two), Some("150"));
+        assert!(ledger.check(Part::Two, "151").is_err());
+    }
+
+    #[test]
+    fn persist_guesses() {
+        let mut ledger = ledger("persist_guesses");
+        ledger.record(Part::One, "7", Verdict::TooLow).unwrap();
+
+        let reopened = AnswerLedger::open_at(&ledger.path).unwrap();
+        assert_eq!(reopened.guesses(Part::One).count(), 1);
+    }
+
+    #[test]
+    fn list_saved_ledgers() {
+        let cache = Cache::new(scratch("list_saved_ledgers"));
+
+        let mut ledger = AnswerLedger::open_at(AnswerLedger::path_in(&cache, 2021, 5)).unwrap();
+        ledger.record(Part::One, "42", Verdict::Correct).unwrap();
+
+        let all = AnswerLedger::all_in(&cache).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!((all[0].0, all[0].1), (2021, 5));
+        assert_eq!(all[0].2.solution(Part::One), Some("42"));
+    }
+}
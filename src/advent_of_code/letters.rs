@@ -0,0 +1,109 @@
+//! Read the capital letters some puzzles draw with dots, 4 columns wide and 6 rows tall
+use std::collections::HashSet;
+
+/// Known glyphs, rows joined together, `#` for a lit dot
+const GLYPHS: &[(char, &str)] = &[
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// Rows of a drawing, `#` for the dots, `.` elsewhere
+pub fn draw(dots: &HashSet<(isize, isize)>) -> Vec<String> {
+    let width = dots.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| if dots.contains(&(x, y)) { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+/// Letters of a drawing, `None` if it is not only made of known letters
+pub fn read(rows: &[String]) -> Option<String> {
+    if rows.len() != 6 {
+        return None;
+    }
+
+    // Letters are separated by an empty column, the last one may be missing
+    let width = rows.iter().map(|row| row.len()).max()?;
+    (0..(width + 1) / 5)
+        .map(|letter| {
+            let glyph: String = rows
+                .iter()
+                .map(|row| {
+                    let row = format!("{:.<1$}", row, width + 1);
+                    row[letter * 5..letter * 5 + 4].to_string()
+                })
+                .collect();
+            GLYPHS
+                .iter()
+                .find(|(_, known)| *known == glyph)
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    fn rows(drawing: &str) -> Vec<String> {
+        drawing
+            .trim()
+            .lines()
+            .map(|l| l.trim().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn read_letters() {
+        let drawing = rows(
+            "
+            #..#.####.###.
+            #..#.#....#..#
+            ####.###..#..#
+            #..#.#....###.
+            #..#.#....#...
+            #..#.####.#...
+            ",
+        );
+        assert_eq!(read(&drawing).as_deref(), Some("HEP"));
+    }
+
+    #[test]
+    fn read_unknown_shape() {
+        let drawing = rows(
+            "
+            #####
+            #...#
+            #...#
+            #...#
+            #####
+            ",
+        );
+        assert_eq!(read(&drawing), None);
+    }
+
+    #[test]
+    fn draw_dots() {
+        let dots = [(0, 0), (2, 1)].into_iter().collect();
+        assert_eq!(draw(&dots), vec!["#..", "..#"]);
+    }
+}
@@ -0,0 +1,641 @@
+//! Offline stand-in for the Advent of Code website, serving fixture files
+//!
+//! Fixtures are laid out as:
+//! - `events.html` and `YEAR/calendar.html` for the calendar
+//! - `YEAR/DAY/input.txt` and `YEAR/DAY/description.html` for the puzzles
+//! - `YEAR/DAY/answers.txt`, the expected answer of each part on its own line
+//! - `YEAR/leaderboard/ID.json` for private leaderboards
+use std::collections::{BTreeSet, VecDeque};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+use super::challenge::unlock_time;
+use crate::prelude::*;
+
+/// A request received by a stand-in server
+#[derive(Debug, Clone, Default)]
+pub struct Request {
+    pub method: String,
+    /// Path of the request, without the query string
+    pub path: String,
+    /// Header names are lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// The request as received
+    pub raw: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.header("cookie")?
+            .split(';')
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(cookie, _)| *cookie == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Field of an url encoded form body
+    pub fn form(&self, name: &str) -> Option<String> {
+        self.body
+            .split('&')
+            .filter_map(|field| field.split_once('='))
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| url_decode(value))
+    }
+}
+
+fn url_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                None => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// A response of a stand-in server
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".into(), "text/html".into())],
+            body: body.into(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.retain(|(header, _)| header != name);
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Read an HTTP/1.1 request: headers up to the empty line, then the body
+pub fn read_request(reader: &mut impl BufRead) -> std::io::Result<Request> {
+    let mut request = Request::default();
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    request.raw.push_str(&line);
+    let mut start = line.split_whitespace();
+    request.method = start.next().unwrap_or_default().to_string();
+    let target = start.next().unwrap_or_default();
+    request.path = target.split('?').next().unwrap_or_default().to_string();
+
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        request.raw.push_str(&line);
+        if line == "\r\n" || line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            request
+                .headers
+                .push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    let content_length = request
+        .header("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    request.body = String::from_utf8_lossy(&content).into_owned();
+    request.raw.push_str(&request.body);
+
+    Ok(request)
+}
+
+/// Write a response, closing the connection after it
+pub fn write_response(writer: &mut impl Write, response: &Response) -> std::io::Result<()> {
+    write!(writer, "HTTP/1.1 {} Mock\r\n", response.status)?;
+    for (name, value) in &response.headers {
+        write!(writer, "{}: {}\r\n", name, value)?;
+    }
+    write!(
+        writer,
+        "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.body.len(),
+        response.body
+    )?;
+    writer.flush()
+}
+
+/// Behaviour of the stand-in website
+#[derive(Debug, Clone)]
+pub struct MockSite {
+    fixtures: PathBuf,
+    secret: String,
+    account: String,
+    now: Option<DateTime<Utc>>,
+}
+
+/// What changes while a [MockServer] runs
+#[derive(Debug, Default)]
+struct State {
+    requests: Vec<Request>,
+    faults: VecDeque<u16>,
+    /// Parts answered correctly: year, day and level
+    solved: BTreeSet<(usize, usize, u8)>,
+}
+
+/// A running [MockSite], serving until the process exits
+#[derive(Debug)]
+pub struct MockServer {
+    url: String,
+    state: Arc<Mutex<State>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockSite {
+    /// Site serving `fixtures`, where only `secret` is a valid session cookie
+    pub fn new(fixtures: impl Into<PathBuf>, secret: impl Into<String>) -> Self {
+        Self {
+            fixtures: fixtures.into(),
+            secret: secret.into(),
+            account: "mock-user".into(),
+            now: None,
+        }
+    }
+
+    /// Fixtures bundled with the crate, in `fixtures/advent_of_code`
+    pub fn bundled(secret: impl Into<String>) -> Self {
+        Self::new(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("fixtures")
+                .join("advent_of_code"),
+            secret,
+        )
+    }
+
+    /// Name of the logged in account
+    pub fn account(mut self, name: impl Into<String>) -> Self {
+        self.account = name.into();
+        self
+    }
+
+    /// Pretend the current time is `now`, puzzles unlocking later are not served
+    pub fn at(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Serve the site on a random local port
+    pub fn start(self) -> Result<MockServer> {
+        self.start_on("127.0.0.1:0")
+    }
+
+    /// Serve the site on the given address
+    pub fn start_on(self, address: &str) -> Result<MockServer> {
+        let listener = TcpListener::bind(address)
+            .with_context(|| format!("Binding mock server to {}", address))?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let shared = state.clone();
+        let handle = std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        warn!("Mock server could not accept connection: {:?}", e);
+                        continue;
+                    }
+                };
+                let request = match read_request(&mut BufReader::new(&stream)) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        warn!("Mock server received an invalid request: {:?}", e);
+                        continue;
+                    }
+                };
+
+                let response = {
+                    let mut state = shared.lock().unwrap();
+                    let response = match state.faults.pop_front() {
+                        Some(status) => fault(status),
+                        None => self.respond(&request, &mut state.solved),
+                    };
+                    debug!("{} {} -> {}", request.method, request.path, response.status);
+                    state.requests.push(request);
+                    response
+                };
+                if let Err(e) = write_response(&mut &stream, &response) {
+                    warn!("Mock server could not respond: {:?}", e);
+                }
+            }
+        });
+
+        Ok(MockServer { url, state, handle })
+    }
+
+    fn logged_in(&self, request: &Request) -> bool {
+        request.cookie("session") == Some(self.secret.as_str())
+    }
+
+    fn unlocked(&self, year: usize, day: usize) -> bool {
+        unlock_time(year, day).map_or(false, |unlock| unlock <= self.now.unwrap_or_else(Utc::now))
+    }
+
+    fn fixture(&self, path: impl AsRef<Path>) -> Option<String> {
+        std::fs::read_to_string(self.fixtures.join(path)).ok()
+    }
+
+    /// Response of the website to a request
+    fn respond(&self, request: &Request, solved: &mut BTreeSet<(usize, usize, u8)>) -> Response {
+        let day: Regex = Regex::new(r"^/(\d+)/day/(\d+)(/input|/answer)?$").unwrap();
+        let leaderboard: Regex =
+            Regex::new(r"^/(\d+)/leaderboard/private/view/(\d+)\.json$").unwrap();
+        let calendar: Regex = Regex::new(r"^/(\d+)$").unwrap();
+
+        let logged_in = self.logged_in(request);
+        let path = request.path.as_str();
+
+        if path == "/" {
+            return Response::new(200, self.home_page(logged_in));
+        }
+        if path == "/events" {
+            return self.fixture("events.html").map_or_else(not_found, ok);
+        }
+        if path.ends_with("/auth/login") {
+            return Response::new(200, self.home_page(false));
+        }
+        if let Some(c) = calendar.captures(path) {
+            return self
+                .fixture(Path::new(&c[1]).join("calendar.html"))
+                .map_or_else(not_found, ok);
+        }
+        if let Some(c) = leaderboard.captures(path) {
+            // The website redirects to its login page instead of answering with an error
+            if !logged_in {
+                return Response::new(302, "")
+                    .with_header("Location", &format!("/{}/auth/login", &c[1]));
+            }
+            return self
+                .fixture(
+                    Path::new(&c[1])
+                        .join("leaderboard")
+                        .join(format!("{}.json", &c[2])),
+                )
+                .map(|json| ok(json).with_header("Content-Type", "application/json"))
+                .unwrap_or_else(not_found);
+        }
+
+        let c = match day.captures(path) {
+            Some(c) => c,
+            None => return not_found(),
+        };
+        let (year, day): (usize, usize) = (c[1].parse().unwrap(), c[2].parse().unwrap());
+        let fixtures = Path::new(&c[1]).join(&c[2]);
+        match c.get(3).map(|m| m.as_str()) {
+            None if !self.unlocked(year, day) => not_found(),
+            None => match self.fixture(fixtures.join("description.html")) {
+                // Logged out visitors only see the first part
+                Some(html) if !logged_in => {
+                    ok(format!("{}</article>", html.split("</article>").next().unwrap()))
+                }
+                Some(html) => ok(html),
+                None => not_found(),
+            },
+            Some("/input") if !logged_in => Response::new(
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            Some("/input") if !self.unlocked(year, day) => Response::new(
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks! \
+                 The calendar countdown is synchronized with the server time; \
+                 the link will be enabled on the calendar the instant this puzzle becomes available.\n",
+            ),
+            Some("/input") => self
+                .fixture(fixtures.join("input.txt"))
+                .map(|input| ok(input).with_header("Content-Type", "text/plain"))
+                .unwrap_or_else(not_found),
+            Some(_) if request.method != "POST" => not_found(),
+            Some(_) if !logged_in => Response::new(400, ""),
+            Some(_) if !self.unlocked(year, day) => not_found(),
+            Some(_) => {
+                let level: u8 = request
+                    .form("level")
+                    .and_then(|level| level.parse().ok())
+                    .unwrap_or(0);
+                let answer = request.form("answer").unwrap_or_default();
+                let expected = self
+                    .fixture(fixtures.join("answers.txt"))
+                    .and_then(|answers| {
+                        answers
+                            .lines()
+                            .nth((level as usize).checked_sub(1)?)
+                            .map(|line| line.trim().to_string())
+                    });
+                match expected {
+                    None => not_found(),
+                    Some(_) if solved.contains(&(year, day, level)) => ok(message(
+                        "You don't seem to be solving the right level.  Did you already complete it?",
+                    )),
+                    Some(expected) if expected == answer.trim() => {
+                        solved.insert((year, day, level));
+                        ok(message("That's the right answer!  You are one gold star closer to saving your vacation."))
+                    }
+                    Some(expected) => {
+                        let hint = match (answer.trim().parse::<i64>(), expected.parse::<i64>()) {
+                            (Ok(given), Ok(expected)) if given > expected => {
+                                "  your answer is too high."
+                            }
+                            (Ok(given), Ok(expected)) if given < expected => {
+                                "  your answer is too low."
+                            }
+                            _ => "",
+                        };
+                        ok(message(&format!(
+                            "That's not the right answer;{}  Please wait one minute before trying again.",
+                            hint
+                        )))
+                    }
+                }
+            }
+        }
+    }
+
+    fn home_page(&self, logged_in: bool) -> String {
+        let user = if logged_in {
+            format!(r#"<div class="user">{}</div>"#, self.account)
+        } else {
+            r#"<div><a href="/auth/login">[Log In]</a></div>"#.to_string()
+        };
+        format!(
+            r#"<html><body><header><h1 class="title-global"><a href="/">Advent of Code</a></h1>{}</header></body></html>"#,
+            user
+        )
+    }
+}
+
+impl MockServer {
+    /// Base URL of the server, to use as `base_url`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Answer the next request with `status` instead of the fixtures, 429 come with a Retry-After
+    pub fn fail_next(&self, status: u16) {
+        self.state.lock().unwrap().faults.push_back(status);
+    }
+
+    /// Requests received so far
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Block until the server stops, which only happens if it panics
+    pub fn join(self) -> Result<()> {
+        self.handle
+            .join()
+            .map_err(|_| anyhow!("Mock server panicked"))
+    }
+}
+
+fn ok(body: String) -> Response {
+    Response::new(200, body)
+}
+
+fn not_found() -> Response {
+    Response::new(404, "404 Not Found")
+}
+
+fn fault(status: u16) -> Response {
+    let response = Response::new(status, format!("Simulated failure: {}", status));
+    if status == 429 {
+        response.with_header("Retry-After", "5")
+    } else {
+        response
+    }
+}
+
+/// Page wrapping a message as the website does after a submission
+fn message(text: &str) -> String {
+    format!(
+        "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+        text
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::advent_of_code::calendar::fetch_calendar;
+    use crate::advent_of_code::challenge::{fetch_daily_challenge, ChallengeError};
+    use crate::advent_of_code::description::fetch_description;
+    use crate::advent_of_code::http::HttpClient;
+    use crate::advent_of_code::leaderboard::fetch_leaderboard;
+    use crate::advent_of_code::session::Session;
+    use crate::advent_of_code::submit::{submit_answer_with, Verdict};
+    use crate::advent_of_code::Part;
+    use reqwest::StatusCode;
+    use test_log::test;
+
+    const SECRET: &str = "mock-secret";
+
+    fn start(site: MockSite) -> (MockServer, Session, Session) {
+        let server = site.start().unwrap();
+        // No retries, the failures are the point of the tests
+        let http = HttpClient::new("test", Duration::ZERO, None, 0);
+        let session = Session {
+            http: http.clone(),
+            ..Session::new(server.url(), SECRET)
+        };
+        let logged_out = Session {
+            http,
+            ..Session::new(server.url(), "expired")
+        };
+        (server, session, logged_out)
+    }
+
+    #[test]
+    fn parse_form_fields() {
+        let request = Request {
+            body: "level=2&answer=a+b%2Fc".into(),
+            ..Default::default()
+        };
+        assert_eq!(request.form("level").as_deref(), Some("2"));
+        assert_eq!(request.form("answer").as_deref(), Some("a b/c"));
+        assert_eq!(request.form("missing"), None);
+    }
+
+    #[test]
+    fn input_served() {
+        let (server, session, _) = start(MockSite::bundled(SECRET));
+        let input = fetch_daily_challenge(&session, 2021, 1).unwrap();
+        assert_eq!(input.lines().count(), 10);
+
+        let request = &server.requests()[0];
+        assert_eq!(request.path, "/2021/day/1/input");
+        assert_eq!(request.cookie("session"), Some(SECRET));
+    }
+
+    #[test]
+    fn input_logged_out() {
+        let (_server, _, logged_out) = start(MockSite::bundled(SECRET));
+        assert!(matches!(
+            fetch_daily_challenge(&logged_out, 2021, 1),
+            Err(ChallengeError::SessionExpired)
+        ));
+    }
+
+    #[test]
+    fn input_before_unlock() {
+        let site = MockSite::bundled(SECRET)
+            .at(unlock_time(2021, 1).unwrap() - chrono::Duration::hours(1));
+        let (_server, session, _) = start(site);
+        match fetch_daily_challenge(&session, 2021, 1) {
+            Err(ChallengeError::NotYetUnlocked { unlocks_at }) => {
+                assert_eq!(unlocks_at, unlock_time(2021, 1).unwrap())
+            }
+            other => panic!("expected NotYetUnlocked, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn input_rate_limited() {
+        let (server, session, _) = start(MockSite::bundled(SECRET));
+        server.fail_next(429);
+        match fetch_daily_challenge(&session, 2021, 1) {
+            Err(ChallengeError::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(5)))
+            }
+            other => panic!("expected RateLimited, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn input_unexpected_status() {
+        let (server, session, _) = start(MockSite::bundled(SECRET));
+        server.fail_next(500);
+        assert!(matches!(
+            fetch_daily_challenge(&session, 2021, 1),
+            Err(ChallengeError::UnexpectedStatus(
+                StatusCode::INTERNAL_SERVER_ERROR
+            ))
+        ));
+    }
+
+    #[test]
+    fn description_served() {
+        let (_server, session, logged_out) = start(MockSite::bundled(SECRET));
+        let description = fetch_description(&session, 2021, 1).unwrap();
+        assert_eq!(description.articles.len(), 2);
+        assert!(description.part(Part::Two).is_some());
+
+        let description = fetch_description(&logged_out, 2021, 1).unwrap();
+        assert_eq!(description.articles.len(), 1);
+    }
+
+    #[test]
+    fn description_errors() {
+        let site = MockSite::bundled(SECRET)
+            .at(unlock_time(2021, 1).unwrap() - chrono::Duration::hours(1));
+        let (server, session, _) = start(site);
+        assert!(fetch_description(&session, 2021, 1)
+            .unwrap_err()
+            .to_string()
+            .contains("not yet available"));
+
+        server.fail_next(500);
+        assert!(fetch_description(&session, 2021, 1).is_err());
+    }
+
+    #[test]
+    fn submit_verdicts() {
+        let (_server, session, _) = start(MockSite::bundled(SECRET));
+        let submit = |part, answer| submit_answer_with(&session, 2021, 1, part, answer).unwrap();
+
+        assert_eq!(submit(Part::One, "1"), Verdict::TooLow);
+        assert_eq!(submit(Part::One, "99999"), Verdict::TooHigh);
+        assert_eq!(submit(Part::One, "7"), Verdict::Correct);
+        assert_eq!(submit(Part::One, "7"), Verdict::AlreadySolved);
+        assert_eq!(submit(Part::Two, "5"), Verdict::Correct);
+    }
+
+    #[test]
+    fn submit_errors() {
+        let (server, session, logged_out) = start(MockSite::bundled(SECRET));
+        assert!(submit_answer_with(&logged_out, 2021, 1, Part::One, "7").is_err());
+
+        server.fail_next(500);
+        assert!(submit_answer_with(&session, 2021, 1, Part::One, "7").is_err());
+    }
+
+    #[test]
+    fn leaderboard_served() {
+        let (_server, session, _) = start(MockSite::bundled(SECRET));
+        let leaderboard = fetch_leaderboard(&session, 2021, 4242).unwrap();
+        assert_eq!(leaderboard.owner_id, 4242);
+        assert_eq!(leaderboard.members.len(), 2);
+    }
+
+    #[test]
+    fn leaderboard_errors() {
+        let (server, session, logged_out) = start(MockSite::bundled(SECRET));
+        assert!(fetch_leaderboard(&logged_out, 2021, 4242)
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid session"));
+        assert!(fetch_leaderboard(&session, 2021, 1)
+            .unwrap_err()
+            .to_string()
+            .contains("not found"));
+
+        server.fail_next(500);
+        assert!(fetch_leaderboard(&session, 2021, 4242).is_err());
+    }
+
+    #[test]
+    fn account_and_calendar() {
+        let (_server, session, logged_out) = start(MockSite::bundled(SECRET).account("alice"));
+        assert_eq!(session.account().unwrap().name, "alice");
+        assert!(logged_out.account().is_err());
+
+        let calendar = fetch_calendar(&session).unwrap();
+        assert_eq!(calendar.years[&2021].total_stars, 2);
+        assert_eq!(calendar.years[&2021].days.get(&1), Some(&2));
+        assert!(calendar.years[&2020].days.is_empty());
+    }
+}
@@ -1,6 +1,35 @@
+pub mod cache;
+pub mod calendar;
 pub mod challenge;
+pub mod description;
+pub mod http;
+pub mod leaderboard;
+pub mod ledger;
+pub mod letters;
+pub mod mock;
 pub mod parsing;
+pub mod prefetch;
 pub mod puzzle;
+pub mod registry;
+pub mod runner;
+pub mod samples;
+pub mod session;
+pub mod source;
+pub mod submit;
+pub mod vault;
 
-pub use self::challenge::daily_challenge;
-pub use self::puzzle::AdventOfCode;
+// Only reached through the registry
+mod solutions;
+
+#[cfg(test)]
+pub(crate) mod testing;
+
+pub use self::cache::Cache;
+pub use self::challenge::{daily_challenge, daily_challenge_when_available, ChallengeError};
+pub use self::description::puzzle_description;
+pub use self::ledger::AnswerLedger;
+pub use self::puzzle::{run_selected_parts, solve_parts, AdventOfCode, Part};
+pub use self::session::{validate_session, Session};
+pub use self::source::ChallengeSource;
+pub use self::submit::{submit_answer, Verdict};
+pub use puzzling_derive::AdventInput;
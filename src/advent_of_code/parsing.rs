@@ -50,3 +50,139 @@ pub fn eager_split(input: String) -> Vec<String> {
     let whitespace: Regex = Regex::new(r"\s+").unwrap();
     whitespace.split(&input.trim()).map(String::from).collect()
 }
+
+// SECTION: FORMAT
+// Used by the code generated by #[derive(AdventInput)], see puzzling_derive
+
+/// Strip a literal part of a format from the start of the input
+#[doc(hidden)]
+pub fn format_literal<'a>(input: &'a str, literal: &str) -> Result<&'a str> {
+    input
+        .strip_prefix(literal)
+        .ok_or_else(|| anyhow!("expected `{}`, found `{}`", literal, input))
+}
+
+/// Parse a field of a format, up to the literal following it or the end of the input
+#[doc(hidden)]
+pub fn format_field<'a, T: FromStr>(
+    input: &'a str,
+    field: &str,
+    until: Option<&str>,
+) -> Result<(T, &'a str)>
+where
+    <T as FromStr>::Err: Into<anyhow::Error>,
+{
+    let (value, rest) = match until {
+        Some(literal) => input
+            .find(literal)
+            .map(|i| input.split_at(i))
+            .ok_or_else(|| {
+                anyhow!(
+                    "expected `{}` after field {}, found `{}`",
+                    literal,
+                    field,
+                    input
+                )
+            })?,
+        None => (input, ""),
+    };
+    let value = value
+        .parse::<T>()
+        .map_err(Into::into)
+        .with_context(|| format!("invalid field {}: `{}`", field, value))?;
+    Ok((value, rest))
+}
+
+/// Check that a format consumed the whole input
+#[doc(hidden)]
+pub fn format_end(rest: &str) -> Result<()> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("unexpected `{}` at the end", rest))
+    }
+}
+
+// !SECTION
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::AdventInput;
+    use test_log::test;
+
+    #[derive(Debug, PartialEq, AdventInput)]
+    #[advent(format = "{x1},{y1} -> {x2},{y2}")]
+    struct Line {
+        x1: usize,
+        y1: usize,
+        x2: usize,
+        y2: usize,
+    }
+
+    #[derive(Debug, PartialEq, AdventInput)]
+    #[advent(format = "{{{0}}}: {1}")]
+    struct Braced(char, String);
+
+    #[derive(Debug, PartialEq, AdventInput)]
+    enum Command {
+        #[advent(format = "forward {0}")]
+        Forward(isize),
+        #[advent(format = "down {amount}")]
+        Down { amount: isize },
+        #[advent(format = "stop")]
+        Stop,
+    }
+
+    #[test]
+    fn derive_struct() {
+        assert_eq!(
+            "0,9 -> 5,9".parse::<Line>().unwrap(),
+            Line {
+                x1: 0,
+                y1: 9,
+                x2: 5,
+                y2: 9
+            }
+        );
+        assert_eq!(
+            "{x}: a, b".parse::<Braced>().unwrap(),
+            Braced('x', "a, b".to_string())
+        );
+    }
+
+    #[test]
+    fn derive_enum() {
+        let commands: Vec<Command> = lines("forward 5\ndown 3\nstop").unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                Command::Forward(5),
+                Command::Down { amount: 3 },
+                Command::Stop
+            ]
+        );
+    }
+
+    #[test]
+    fn derive_errors() {
+        let error = format!("{:#}", "0,9 -> 5,x".parse::<Line>().unwrap_err());
+        assert_eq!(
+            error,
+            "`0,9 -> 5,x` does not match the format of Line: \
+             invalid field y2: `x`: invalid digit found in string"
+        );
+
+        let error = format!("{:#}", "0,9 => 5,9".parse::<Line>().unwrap_err());
+        assert!(error.contains("expected ` -> ` after field y1, found `9 => 5,9`"));
+
+        let error = "stop now".parse::<Command>().unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "`stop now` matches no format of Command:\n  \
+             Forward: expected `forward `, found `stop now`\n  \
+             Down: expected `down `, found `stop now`\n  \
+             Stop: unexpected ` now` at the end"
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::Mutex;
+
+use chrono::Utc;
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::cache::Cache;
+use super::challenge::unlock_time;
+use super::description::{description_path, fetch_description};
+use super::leaderboard::days_in_event;
+use super::session::ensure_valid_session;
+use super::source::{ChallengeSource, HttpSource};
+use crate::prelude::*;
+
+/// Outcome of [prefetch_year]
+#[derive(Debug, Clone, Default)]
+pub struct PrefetchReport {
+    pub fetched: Vec<usize>,
+    pub skipped: Vec<usize>,
+    pub failed: Vec<(usize, String)>,
+}
+
+/// Days of an event already unlocked
+pub fn unlocked_days(year: usize) -> Vec<usize> {
+    let now = Utc::now();
+    (1..=days_in_event(year) as usize)
+        .filter(|day| unlock_time(year, *day).map_or(false, |unlock| unlock <= now))
+        .collect()
+}
+
+/// Download the input and description of every unlocked day of an event into the cache
+///
+/// At most `jobs` days are fetched at once, requests are spaced by the shared HTTP client.
+pub fn prefetch_year(year: usize, jobs: usize) -> Result<PrefetchReport> {
+    ensure_valid_session()?;
+    prefetch_days(
+        &HttpSource::from_settings()?,
+        year,
+        &unlocked_days(year),
+        jobs,
+    )
+}
+
+/// Download the inputs and descriptions missing from the cache of `source`
+fn prefetch_days(
+    source: &HttpSource,
+    year: usize,
+    days: &[usize],
+    jobs: usize,
+) -> Result<PrefetchReport> {
+    let cache = &source.cache;
+    let progress = ProgressBar::new(days.len() as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{msg} [{wide_bar}] {pos}/{len}")
+            .expect("valid progress template"),
+    );
+    progress.set_message(format!("Fetching {}", year));
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.max(1))
+        .build()
+        .context("Building prefetch thread pool")?;
+
+    let report = Mutex::new(PrefetchReport::default());
+    pool.install(|| {
+        days.par_iter().for_each(|day| {
+            let has_input = cache
+                .root()
+                .join(Cache::challenge_path(year, *day))
+                .exists();
+            let has_description = cache.root().join(description_path(year, *day)).exists();
+
+            let result = if has_input && has_description {
+                None
+            } else {
+                Some((|| -> Result<()> {
+                    if !has_input {
+                        source.challenge(year, *day)?;
+                    }
+                    if !has_description {
+                        let description = fetch_description(&source.session, year, *day)?;
+                        cache.write(description_path(year, *day), &description.to_html())?;
+                    }
+                    Ok(())
+                })())
+            };
+
+            let mut report = report.lock().unwrap();
+            match result {
+                None => report.skipped.push(*day),
+                Some(Ok(())) => report.fetched.push(*day),
+                Some(Err(e)) => {
+                    progress.println(format!("Day {}: {:?}", day, e));
+                    report.failed.push((*day, e.to_string()));
+                }
+            }
+            progress.inc(1);
+        })
+    });
+    progress.finish_and_clear();
+
+    let mut report = report.into_inner().unwrap();
+    report.fetched.sort_unstable();
+    report.skipped.sort_unstable();
+    report.failed.sort_unstable();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::advent_of_code::http::HttpClient;
+    use crate::advent_of_code::mock::MockSite;
+    use crate::advent_of_code::session::Session;
+    use crate::advent_of_code::testing::scratch;
+    use test_log::test;
+
+    #[test]
+    fn skip_cached_days() {
+        let server = MockSite::bundled("secret").start().unwrap();
+        let root = scratch("skip_cached_days");
+        let source = HttpSource {
+            session: Session {
+                http: HttpClient::new("test", Duration::ZERO, None, 0),
+                ..Session::new(server.url(), "secret")
+            },
+            cache: Cache::new(root),
+        };
+        source
+            .cache
+            .write(Cache::challenge_path(2021, 2), "forward 5")
+            .unwrap();
+        source
+            .cache
+            .write(
+                description_path(2021, 2),
+                "<article class=\"day-desc\"></article>",
+            )
+            .unwrap();
+
+        let report = prefetch_days(&source, 2021, &[1, 2], 2).unwrap();
+        assert_eq!(report.fetched, [1]);
+        assert_eq!(report.skipped, [2]);
+        assert!(server
+            .requests()
+            .iter()
+            .all(|request| request.path.starts_with("/2021/day/1")));
+
+        let requests = server.requests().len();
+        let report = prefetch_days(&source, 2021, &[1, 2], 2).unwrap();
+        assert_eq!(report.skipped, [1, 2]);
+        assert_eq!(server.requests().len(), requests);
+    }
+}
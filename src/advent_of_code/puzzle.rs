@@ -1,10 +1,171 @@
+use std::fmt::Display;
+use std::time::Instant;
+
 use crate::prelude::*;
+use serde_derive::{Deserialize, Serialize};
 
+/// A daily puzzle, each part has its own answer type: a count and a drawn word for instance
 pub trait AdventOfCode {
     type Input: FromStr;
-    type Answer;
+    type AnswerOne: Display;
+    type AnswerTwo: Display;
     type Puzzle: Sized + From<Vec<Self::Input>>;
+    /// Work shared by both parts, `()` when there is none
+    type State;
+
+    /// Work run once before the parts, timed separately by the runner, e.g. a simulation
+    /// whose outcome both parts look at
+    fn prepare(&self) -> Result<Self::State>;
+
+    fn part_one(&self, state: &Self::State) -> Result<Self::AnswerOne>;
+    fn part_two(&self, state: &Self::State) -> Result<Self::AnswerTwo>;
+}
+
+/// Prepare the puzzle then print the answer of `part`, or both, a failing part is reported with
+/// its error chain and does not prevent the other one from running
+///
+/// Returns whether the parts were solved.
+pub fn run_selected_parts<T: AdventOfCode>(puzzle: &T, part: Option<Part>) -> bool {
+    solve_parts(puzzle, part)
+        .iter()
+        .all(|(_, answer)| answer.is_some())
+}
+
+/// Same as [run_selected_parts], returning the answer of each selected part, `None` when it
+/// failed
+pub fn solve_parts<T: AdventOfCode>(puzzle: &T, part: Option<Part>) -> Vec<(Part, Option<String>)> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+
+    let timeit = Instant::now();
+    let state = match puzzle.prepare() {
+        Ok(state) => state,
+        Err(e) => {
+            error!("Preparation failed: {:?}", e);
+            return parts.into_iter().map(|part| (part, None)).collect();
+        }
+    };
+    info!("Preparation took {:.04}s", timeit.elapsed().as_secs_f64());
+
+    parts
+        .into_iter()
+        .map(|part| {
+            let answer = match part {
+                Part::One => report_part(part, || puzzle.part_one(&state)),
+                Part::Two => report_part(part, || puzzle.part_two(&state)),
+            };
+            (part, answer)
+        })
+        .collect()
+}
+
+fn report_part<A: Display>(part: Part, solve: impl FnOnce() -> Result<A>) -> Option<String> {
+    let timeit = Instant::now();
+    let answer = solve();
+    info!("Part {} took {:.04}s", part, timeit.elapsed().as_secs_f64());
+
+    match answer {
+        Ok(answer) => {
+            println!("Part {}: {}", part, answer);
+            Some(answer.to_string())
+        }
+        Err(e) => {
+            error!("Part {} failed: {:?}", part, e);
+            None
+        }
+    }
+}
+
+/// Each daily puzzle is made of two parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Level as understood by the website
+    pub fn level(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => Err(anyhow!("unknown part: {} (expected 1 or 2)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.level())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    /// A puzzle failing its first part, or its preparation when `broken`
+    struct Stub {
+        broken: bool,
+    }
+
+    impl From<Vec<usize>> for Stub {
+        fn from(_: Vec<usize>) -> Self {
+            Stub { broken: false }
+        }
+    }
+
+    impl AdventOfCode for Stub {
+        type Input = usize;
+        type AnswerOne = usize;
+        type AnswerTwo = usize;
+        type Puzzle = Stub;
+        type State = usize;
+
+        fn prepare(&self) -> Result<usize> {
+            if self.broken {
+                return Err(anyhow!("broken"));
+            }
+            Ok(42)
+        }
+
+        fn part_one(&self, _: &usize) -> Result<usize> {
+            Err(anyhow!("not solved yet"))
+        }
+
+        fn part_two(&self, state: &usize) -> Result<usize> {
+            Ok(*state)
+        }
+    }
+
+    #[test]
+    fn failing_part_does_not_stop_the_other() {
+        let stub = Stub { broken: false };
+        assert_eq!(
+            solve_parts(&stub, None),
+            [(Part::One, None), (Part::Two, Some("42".to_string()))]
+        );
+        assert!(!run_selected_parts(&stub, None));
+        assert!(run_selected_parts(&stub, Some(Part::Two)));
+    }
 
-    fn part_one(&self) -> Self::Answer;
-    fn part_two(&self) -> Self::Answer;
+    #[test]
+    fn failing_preparation_fails_every_part() {
+        let stub = Stub { broken: true };
+        assert_eq!(solve_parts(&stub, Some(Part::Two)), [(Part::Two, None)]);
+    }
 }
@@ -0,0 +1,52 @@
+//! Solutions registered with [advent_solution](crate::advent_solution), so that a single binary
+//! can run any day
+//!
+//! Only the days of the enabled `advent_YYYY` features are compiled, thus registered.
+use super::puzzle::Part;
+
+/// The [AdventOfCode](super::AdventOfCode) implementation solving a day
+pub struct Solution {
+    pub year: usize,
+    pub day: usize,
+    /// Solve the day from an input spec (see `--input`), for a part or both, and submit the
+    /// answers when asked, returns whether every part was solved
+    pub run: fn(Option<&str>, Option<Part>, bool) -> bool,
+}
+
+inventory::collect!(Solution);
+
+/// Every registered solution, ordered by year and day
+pub fn solutions() -> Vec<&'static Solution> {
+    let mut solutions = inventory::iter::<Solution>.into_iter().collect::<Vec<_>>();
+    solutions.sort_by_key(|solution| (solution.year, solution.day));
+    solutions
+}
+
+/// Solutions of a year, or of a single day
+pub fn find(year: usize, day: Option<usize>) -> Vec<&'static Solution> {
+    solutions()
+        .into_iter()
+        .filter(|solution| solution.year == year && day.map_or(true, |day| solution.day == day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    #[cfg(feature = "advent_2021")]
+    fn list_registered_solutions() {
+        let days = find(2021, None)
+            .iter()
+            .map(|solution| solution.day)
+            .collect::<Vec<_>>();
+        assert!(days.contains(&1));
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+
+        let single = find(2021, Some(1));
+        assert_eq!(single.len(), 1);
+        assert_eq!((single[0].year, single[0].day), (2021, 1));
+    }
+}
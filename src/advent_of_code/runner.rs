@@ -0,0 +1,82 @@
+//! Solving a day, as registered by [advent_solution](crate::advent_solution)
+use super::puzzle::{solve_parts, AdventOfCode, Part};
+use super::source::configured_source;
+use super::submit::{submit_answer, Verdict};
+use crate::prelude::*;
+
+/// Solve a day from the given input spec (see [configured_source]), only `part` if given, and
+/// submit the answers to the website if asked
+///
+/// Returns whether every part was solved.
+pub fn run_day<T>(
+    year: usize,
+    day: usize,
+    input: Option<&str>,
+    part: Option<Part>,
+    submit_answers: bool,
+) -> bool
+where
+    T: AdventOfCode + FromStr<Err = Error>,
+{
+    let puzzle: T = match configured_source(input)
+        .and_then(|source| source.challenge(year, day))
+        .map_err(Error::from)
+        .and_then(|input| input.parse().context("Parsing input"))
+    {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            error!("Could not load the puzzle of {}/{}: {:?}", year, day, e);
+            return false;
+        }
+    };
+
+    let answers = solve_parts(&puzzle, part);
+    let solved = answers.iter().all(|(_, answer)| answer.is_some());
+
+    if submit_answers {
+        for (part, answer) in answers {
+            if let Some(answer) = answer {
+                submit(year, day, part, &answer);
+            }
+        }
+    }
+
+    solved
+}
+
+fn submit(year: usize, day: usize, part: Part, answer: &str) {
+    match submit_answer(year, day, part, answer) {
+        Ok(Verdict::Correct) => info!("Part {}: <green>That's the right answer!</>", part),
+        Ok(Verdict::RateLimited(wait)) => {
+            warn!(
+                "Part {}: submitted too recently, wait {}s",
+                part,
+                wait.as_secs()
+            )
+        }
+        Ok(verdict) => warn!("Part {}: answer {} is {}", part, answer, verdict),
+        Err(e) => error!("Part {}: could not submit answer: {:?}", part, e),
+    }
+}
+
+/// Register the [AdventOfCode] implementation solving a day, run by `advent run`
+///
+/// ```ignore
+/// puzzling::advent_solution!(2021, 5, Puzzle);
+/// ```
+#[macro_export]
+macro_rules! advent_solution {
+    ($year:expr, $day:expr, $puzzle:ty) => {
+        $crate::inventory::submit! {
+            $crate::advent_of_code::registry::Solution {
+                year: $year,
+                day: $day,
+                run: |input, part, submit| {
+                    $crate::advent_of_code::runner::run_day::<$puzzle>(
+                        $year, $day, input, part, submit,
+                    )
+                },
+            }
+        }
+    };
+}
@@ -0,0 +1,239 @@
+use regex::Regex;
+
+use super::description::{decode_entities, Description};
+use super::puzzle::Part;
+use crate::prelude::*;
+
+/// An example input found in a puzzle description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub part: Part,
+    pub input: String,
+    /// Emphasized value that looks like the expected answer
+    pub expected: Option<String>,
+    /// Whether input and expected answer could be paired without guessing
+    pub confident: bool,
+}
+
+/// Strip markup from a fragment of HTML
+fn text(html: &str) -> String {
+    let tags: Regex = Regex::new(r"<[^>]*>").unwrap();
+    decode_entities(&tags.replace_all(html, ""))
+}
+
+/// Code blocks of an article
+fn code_blocks(article: &str) -> Vec<String> {
+    let pre: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    pre.captures_iter(article)
+        .map(|c| text(&c[1]))
+        .filter(|block| !block.trim().is_empty())
+        .collect()
+}
+
+/// Last emphasized value of an article, the website states the answer of the example last
+fn expected_answer(article: &str) -> Option<String> {
+    let emphasized: Regex =
+        Regex::new(r"<code><em>([^<]+)</em></code>|<em><code>([^<]+)</code></em>|<em>(-?\d+)</em>")
+            .unwrap();
+    let outside_pre: Regex = Regex::new(r"(?s)<pre>.*?</pre>").unwrap();
+
+    emphasized
+        .captures_iter(&outside_pre.replace_all(article, ""))
+        .filter_map(|c| c.get(1).or(c.get(2)).or(c.get(3)))
+        .map(|m| decode_entities(m.as_str()))
+        .last()
+}
+
+/// Pair example inputs with their expected answers
+///
+/// A pairing is confident when the part has a single example input (part two reuses the
+/// example of part one) and a numeric expected answer.
+pub fn extract_samples(description: &Description) -> Vec<Sample> {
+    let first_blocks = description
+        .part(Part::One)
+        .map(code_blocks)
+        .unwrap_or_default();
+
+    let mut samples = vec![];
+    for part in [Part::One, Part::Two] {
+        let article = match description.part(part) {
+            Some(article) => article,
+            None => continue,
+        };
+
+        let mut blocks = code_blocks(article);
+        if blocks.is_empty() && part == Part::Two {
+            blocks = first_blocks.clone();
+        }
+
+        let expected = expected_answer(article);
+        let numeric = expected
+            .as_ref()
+            .map_or(false, |e| e.parse::<i128>().is_ok());
+        let confident = blocks.len() == 1 && numeric;
+
+        samples.extend(blocks.into_iter().map(|input| Sample {
+            part,
+            input,
+            expected: expected.clone(),
+            confident,
+        }));
+    }
+    samples
+}
+
+/// Test cases, in the style of `templates/advent.rs`, for the samples of a part
+///
+/// Samples that are not confidently paired are emitted commented out.
+pub fn sample_tests(samples: &[Sample], part: Part) -> String {
+    let name = match part {
+        Part::One => "one",
+        Part::Two => "two",
+    };
+
+    samples
+        .iter()
+        .filter(|s| s.part == part)
+        .enumerate()
+        .map(|(index, sample)| {
+            let hashes = "#".repeat(
+                (1..)
+                    .find(|n| !sample.input.contains(&format!("\"{}", "#".repeat(*n))))
+                    .unwrap(),
+            );
+            let input = sample
+                .input
+                .trim_end()
+                .lines()
+                .map(|line| format!("        {}", line).trim_end().to_string())
+                .join("\n");
+            let expected = sample.expected.clone().unwrap_or_else(|| "todo!()".into());
+
+            let test = format!(
+                r#"/// Solver PART {name}
+/// Test Case: sample_{index}
+#[test]
+fn part_{name}_sample_{index}() {{
+    let puzzle: Puzzle = r{hashes}"
+{input}
+    "{hashes}
+    .parse()
+    .expect("failed to parse input string");
+
+    assert_eq!(puzzle.part_{name}(&puzzle.prepare().unwrap()).unwrap(), {expected});
+}}"#,
+                name = name,
+                index = index + 1,
+                hashes = hashes,
+                input = input,
+                expected = expected,
+            );
+
+            // Indent in the tests module, and comment out the uncertain pairings
+            let prefix = if sample.confident { "" } else { "// " };
+            test.lines()
+                .map(|line| match line {
+                    "" => "".to_string(),
+                    _ => format!("        {}{}", prefix, line),
+                })
+                .join("\n")
+                + "\n"
+        })
+        .join("\n")
+}
+
+/// Insert the sample tests of both parts in the sections of a generated file
+pub fn insert_sample_tests(contents: &str, samples: &[Sample]) -> String {
+    let mut contents = contents.to_string();
+    for (part, marker) in [
+        (Part::One, "// SECTION: PART ONE\n"),
+        (Part::Two, "// SECTION: PART TWO\n"),
+    ] {
+        let tests = sample_tests(samples, part);
+        if tests.is_empty() {
+            continue;
+        }
+        contents = contents.replacen(marker, &format!("{}\n{}", marker, tests), 1);
+    }
+    contents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    fn description() -> Description {
+        Description {
+            articles: vec![
+                r#"<p>For example:</p><pre><code>199
+200
+</code></pre><p>There are <em>7</em> measurements.</p>"#
+                    .into(),
+                r#"<p>Consider <em>sums</em> instead.</p><p>There are <em>5</em> sums.</p>"#.into(),
+            ],
+        }
+    }
+
+    #[test]
+    fn pair_samples() {
+        let samples = extract_samples(&description());
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].input, "199\n200\n");
+        assert_eq!(samples[0].expected.as_deref(), Some("7"));
+        assert!(samples[0].confident);
+        assert_eq!(samples[1].part, Part::Two);
+        assert_eq!(samples[1].expected.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn uncertain_samples_are_commented() {
+        let mut samples = extract_samples(&description());
+        samples[1].confident = false;
+
+        let one = sample_tests(&samples, Part::One);
+        assert!(one.contains("        #[test]\n        fn part_one_sample_1() {"));
+        assert!(one.contains("                199\n                200\n"));
+        assert!(
+            one.contains("assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 7);")
+        );
+
+        let two = sample_tests(&samples, Part::Two);
+        assert!(two
+            .lines()
+            .filter(|l| !l.is_empty())
+            .all(|l| l.starts_with("        // ")));
+    }
+
+    #[test]
+    fn generated_test() {
+        let samples = extract_samples(&description());
+        assert_eq!(
+            sample_tests(&samples, Part::Two),
+            [
+                "        /// Solver PART two",
+                "        /// Test Case: sample_1",
+                "        #[test]",
+                "        fn part_two_sample_1() {",
+                "            let puzzle: Puzzle = r#\"",
+                "                199",
+                "                200",
+                "            \"#",
+                "            .parse()",
+                "            .expect(\"failed to parse input string\");",
+                "",
+                "            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 5);",
+                "        }",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn insert_in_sections() {
+        let template = "        // SECTION: PART ONE\n\n        // !SECTION\n";
+        let contents = insert_sample_tests(template, &extract_samples(&description()));
+        assert!(contents.contains("fn part_one_sample_1()"));
+    }
+}
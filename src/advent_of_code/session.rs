@@ -0,0 +1,201 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use regex::Regex;
+use reqwest::{blocking::Response, StatusCode};
+use sha2::{Digest, Sha256};
+
+use super::cache::Cache;
+use super::http::{self, HttpClient};
+use crate::config::Settings;
+use crate::prelude::*;
+
+/// Authenticated access to the Advent of Code website
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// Root of the website, without trailing slash
+    pub base_url: String,
+    /// Value of the `session` cookie
+    pub secret: String,
+    /// Every request to the website goes through it
+    pub http: HttpClient,
+}
+
+/// Short identifier of a session secret, to name things after it without revealing it
+pub(crate) fn fingerprint(secret: &str) -> String {
+    Sha256::digest(secret.as_bytes())
+        .iter()
+        .take(6)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// The account a session is logged in as
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub name: String,
+    /// AoC++ badge
+    pub supporter: bool,
+    /// Stars of the current event, as shown in the header
+    pub stars: Option<u32>,
+}
+
+/// Result of [validate_session]
+#[derive(Debug, Clone)]
+pub struct SessionStatus {
+    pub account: Account,
+    /// First time this session was seen valid
+    pub first_seen: DateTime<Utc>,
+    /// Based on `session_lifetime` days after `first_seen`
+    pub estimated_expiry: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn new(base_url: impl Into<String>, secret: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            secret: secret.into(),
+            http: HttpClient::default(),
+        }
+    }
+
+    /// Session described by the `[advent_of_code]` section of `puzzling.toml`
+    pub fn from_settings() -> Result<Self> {
+        let settings = &Settings::get().advent_of_code;
+        Ok(Self {
+            http: http::shared().clone(),
+            ..Self::new(settings.base_url(), settings.session_secret()?)
+        })
+    }
+
+    /// Absolute URL of a page of the website
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    /// Value for the `Cookie` header
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.secret)
+    }
+
+    /// Authenticated GET of a page of the website
+    pub fn get(&self, path: &str) -> reqwest::Result<Response> {
+        self.http.send(
+            self.http
+                .client()
+                .get(self.url(path))
+                .header(reqwest::header::COOKIE, self.cookie()),
+        )
+    }
+
+    /// Authenticated POST of a form to the website
+    pub fn post_form(&self, path: &str, form: &[(&str, String)]) -> reqwest::Result<Response> {
+        self.http.send(
+            self.http
+                .client()
+                .post(self.url(path))
+                .header(reqwest::header::COOKIE, self.cookie())
+                .form(form),
+        )
+    }
+
+    /// Account the session is logged in as, an error is returned for invalid sessions
+    pub fn account(&self) -> Result<Account> {
+        let res = self.get("/").context("Failed to send request")?;
+
+        match res.status() {
+            StatusCode::OK => parse_account(&res.text().context("Failed to read response body")?),
+            unhandled => match res.error_for_status() {
+                Ok(_) => Err(anyhow!("Expected to fail for status: {:?}", unhandled)),
+                Err(e) => Err(e).with_context(|| format!("Unhandled HTTP error: {:?}", unhandled)),
+            },
+        }
+    }
+}
+
+/// Extract the logged in account from the header of any page
+pub fn parse_account(html: &str) -> Result<Account> {
+    let user: Regex = Regex::new(r#"(?s)<div class="user">(.*?)</div>"#).unwrap();
+    let stars: Regex = Regex::new(r#"<span class="star-count">(\d+)\*</span>"#).unwrap();
+
+    let header = user
+        .captures(html)
+        .map(|c| c[1].to_string())
+        .ok_or(anyhow!("Invalid or expired session"))?;
+
+    let name = header.split('<').next().unwrap_or_default().trim();
+    Ok(Account {
+        name: super::description::decode_entities(name),
+        supporter: header.contains("supporter-badge"),
+        stars: stars.captures(&header).and_then(|c| c[1].parse().ok()),
+    })
+}
+
+/// Check the configured session and estimate when it will expire
+///
+/// The website does not tell when the cookie expires, so the first time a session is seen
+/// valid is remembered in the cache, and the `session_lifetime` setting is added to it. It is
+/// kept per cookie, so a new cookie starts a new lifetime.
+pub fn validate_session() -> Result<SessionStatus> {
+    let session = Session::from_settings()?;
+    let account = session.account().context("Validating session")?;
+
+    let cache = Cache::from_settings();
+    let seen = format!("session.{}.seen", fingerprint(&session.secret));
+    let first_seen = cache
+        .read(&seen)
+        .ok()
+        .and_then(|seen| seen.trim().parse().ok())
+        .and_then(|seen| Utc.timestamp_opt(seen, 0).single());
+    let first_seen = match first_seen {
+        Some(first_seen) => first_seen,
+        None => {
+            let now = Utc::now();
+            if let Err(e) = cache.write(&seen, &now.timestamp().to_string()) {
+                warn!("Could not remember session: {:?}", e);
+            }
+            now
+        }
+    };
+
+    Ok(SessionStatus {
+        account,
+        first_seen,
+        estimated_expiry: first_seen
+            + Duration::days(Settings::get().advent_of_code.session_lifetime as i64),
+    })
+}
+
+/// Fail early, before long operations, when the session is not usable
+pub fn ensure_valid_session() -> Result<()> {
+    let status = validate_session()?;
+    info!("Logged in as {}", status.account.name);
+    if status.estimated_expiry < Utc::now() + Duration::days(1) {
+        warn!(
+            "Session may expire soon (estimated {})",
+            status.estimated_expiry
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::testing::serve_once;
+    use test_log::test;
+
+    const HEADER: &str = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><div class="user">Larandar &amp; co <a href="/2021/support" class="supporter-badge" title="Advent of Code Supporter">AoC++</a> <span class="star-count">34*</span></div></div></header>"#;
+
+    #[test]
+    fn parse_logged_in_header() {
+        let account = parse_account(HEADER).unwrap();
+        assert_eq!(account.name, "Larandar & co");
+        assert!(account.supporter);
+        assert_eq!(account.stars, Some(34));
+    }
+
+    #[test]
+    fn reject_logged_out_page() {
+        let (url, _) = serve_once(200, r#"<a href="/2021/auth/login">[Log In]</a>"#);
+        assert!(Session::new(url, "expired").account().is_err());
+    }
+}
@@ -1,15 +1,16 @@
-use puzzling::advent_of_code::{daily_challenge, AdventOfCode};
+use puzzling::advent_of_code::AdventOfCode;
 use puzzling::prelude::*;
 
 type Input = usize;
-type Answer = usize;
+type AnswerOne = usize;
+type AnswerTwo = usize;
 
 #[derive(Debug, Clone)]
 struct Puzzle {
     modules: Vec<Input>,
 }
 
-// TODO(macro): Add derive macro for standard implementation of FromStr
+/// Implement parsing a Puzzle struct from an input string
 impl FromStr for Puzzle {
     type Err = Error;
 
@@ -31,42 +32,36 @@ impl From<Vec<Input>> for Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
 
-    fn part_one(&self) -> Self::Answer {
-        self.modules
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
+
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
+        Ok(self
+            .modules
             .iter()
-            .map(|m| -> Answer { ((*m as f64) / 3.).floor() as Input - 2 })
-            .sum()
+            .map(|m| -> AnswerOne { ((*m as f64) / 3.).floor() as Input - 2 })
+            .sum())
     }
 
-    fn part_two(&self) -> Self::Answer {
-        fn fuel_needed(module: Input) -> Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
+        fn fuel_needed(module: Input) -> AnswerTwo {
             let fuel = ((module as f64) / 3.).floor() as isize - 2;
             match fuel {
                 1.. => fuel as Input + fuel_needed(fuel as Input),
                 _ => 0,
             }
         }
-        self.modules.iter().map(|m| fuel_needed(*m)).sum()
+        Ok(self.modules.iter().map(|m| fuel_needed(*m)).sum())
     }
 }
 
-// TODO(macro): make reusable code be generated by macro
-// TODO(bootstrap): initialize and use log crate
-fn main() -> Result<()> {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle = daily_challenge(2019, 01)?.parse::<Puzzle>()?;
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-
-    Ok(())
-}
+puzzling::advent_solution!(2019, 01, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2019")]
@@ -75,7 +70,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2019_01 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
 
         // SECTION: PART ONE
@@ -86,7 +81,7 @@ mod tests {
         fn part_one_sample_1() {
             let puzzle: Puzzle = r#"12"#.parse().expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 2);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 2);
         }
 
         /// Solver: Puzzle::part_one
@@ -95,7 +90,7 @@ mod tests {
         fn part_one_sample_2() {
             let puzzle: Puzzle = r#"14"#.parse().expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 2);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 2);
         }
 
         /// Solver: Puzzle::part_one
@@ -104,7 +99,7 @@ mod tests {
         fn part_one_sample_3() {
             let puzzle: Puzzle = r#"1969"#.parse().expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 654);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 654);
         }
 
         /// Solver: Puzzle::part_one
@@ -113,7 +108,7 @@ mod tests {
         fn part_one_sample_4() {
             let puzzle: Puzzle = r#"100756"#.parse().expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 33583);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 33583);
         }
 
         // !SECTION
@@ -1,11 +1,14 @@
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = usize;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -15,7 +18,7 @@ struct Puzzle {
 
 /// Implement parsing a Puzzle struct from an input string
 ///
-/// TODO(macro): Add derive macro for standard implementation of FromStr
+/// Implement parsing a Puzzle struct from an input string
 impl FromStr for Puzzle {
     type Err = Error;
 
@@ -37,37 +40,30 @@ impl From<Vec<Input>> for Puzzle {
 /// Solver implementation
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
-        (0..self.lines.len() - 1)
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
+        Ok((0..self.lines.len() - 1)
             .filter(|i| self.lines[*i] < self.lines[*i + 1])
-            .count()
+            .count())
     }
 
-    fn part_two(&self) -> Self::Answer {
-        let w = |i| self.lines[i..i + 3].iter().sum::<Answer>();
-        (0..self.lines.len() - 3)
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
+        let w = |i| self.lines[i..i + 3].iter().sum::<AnswerTwo>();
+        Ok((0..self.lines.len() - 3)
             .filter(|i| w(*i) < w(*i + 1))
-            .count()
+            .count())
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 01)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 01, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -76,7 +72,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_01 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
 
         // SECTION: PART ONE
@@ -100,7 +96,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 7);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 7);
         }
 
         // !SECTION
@@ -126,7 +122,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 5);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 5);
         }
 
         // !SECTION
@@ -1,34 +1,24 @@
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventInput, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
-#[derive(Debug, Clone)]
+///
+/// Formats are lowercase, the puzzle lowercases its input to accept orders in any case
+#[derive(Debug, Clone, AdventInput)]
 enum Input {
+    #[advent(format = "forward {0}")]
     Forward(isize),
+    #[advent(format = "down {0}")]
     Down(isize),
+    #[advent(format = "up {0}")]
     Up(isize),
 }
 
-impl FromStr for Input {
-    type Err = Error;
+/// Expected output of part one
+type AnswerOne = isize;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((o, d)) = s.split(" ").tuples().next() {
-            let d = d.parse()?;
-            match o.to_lowercase().as_str() {
-                "forward" => Ok(Input::Forward(d)),
-                "down" => Ok(Input::Down(d)),
-                "up" => Ok(Input::Up(d)),
-                _ => Err(anyhow!("unknow order: {}", s)),
-            }
-        } else {
-            Err(anyhow!("unknow order: {}", s))
-        }
-    }
-}
-
-/// Expected output
-type Answer = isize;
+/// Expected output of part two
+type AnswerTwo = isize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -37,14 +27,12 @@ struct Puzzle {
 }
 
 /// Implement parsing a Puzzle struct from an input string
-///
-/// TODO(macro): Add derive macro for standard implementation of FromStr
 impl FromStr for Puzzle {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        // Standard parsing of input
-        parsing::lines::<Input>(s)
+        // Standard parsing of input, orders are not case sensitive
+        parsing::lines::<Input>(&s.to_lowercase())
             // Creation using the From<Vec<Input>> input
             .map(|lines: Vec<Input>| -> Self { lines.into() })
     }
@@ -60,19 +48,25 @@ impl From<Vec<Input>> for Puzzle {
 /// Solver implementation
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
 
-    fn part_one(&self) -> Self::Answer {
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
+
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         let (x, depth) = self.orders.iter().fold((0, 0), |(x, depth), o| match o {
             Input::Forward(v) => (x + v, depth),
             Input::Down(v) => (x, depth + v),
             Input::Up(v) => (x, depth - v),
         });
-        x * depth
+        Ok(x * depth)
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         let (_, x, depth) = self
             .orders
             .iter()
@@ -81,24 +75,11 @@ impl AdventOfCode for Puzzle {
                 Input::Down(v) => (aim - v, x, depth),
                 Input::Up(v) => (aim + v, x, depth),
             });
-        -x * depth
+        Ok(-x * depth)
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 02)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 02, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -107,7 +88,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_01 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
 
         // SECTION: PART ONE
@@ -127,7 +108,16 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 150);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 150);
+        }
+
+        #[test]
+        fn part_one_any_case() {
+            let puzzle: Puzzle = "Forward 5\nDOWN 5\nup 3"
+                .parse()
+                .expect("failed to parse input string");
+
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 10);
         }
 
         // !SECTION
@@ -149,7 +139,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 900);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 900);
         }
 
         // !SECTION
@@ -1,11 +1,14 @@
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = u128;
+/// Expected output of part one
+type AnswerOne = u128;
+
+/// Expected output of part two
+type AnswerTwo = u128;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -15,7 +18,7 @@ struct Puzzle {
 
 /// Implement parsing a Puzzle struct from an input string
 ///
-/// TODO(macro): Add derive macro for standard implementation of FromStr
+/// Implement parsing a Puzzle struct from an input string
 impl FromStr for Puzzle {
     type Err = Error;
 
@@ -37,10 +40,16 @@ impl From<Vec<Input>> for Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         let v = (0..self.lines.first().unwrap().len())
             .map(|i| {
                 self.lines
@@ -65,10 +74,10 @@ impl AdventOfCode for Puzzle {
             2,
         )
         .unwrap();
-        gamma * epsilon
+        Ok(gamma * epsilon)
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         let mut oxy_rating = self.lines.clone();
         for i in 0..oxy_rating.first().unwrap().len() {
             let bit_crit = oxy_rating
@@ -90,7 +99,8 @@ impl AdventOfCode for Puzzle {
                 break;
             }
         }
-        let oxy_rating = Answer::from_str_radix(oxy_rating.first().unwrap().as_str(), 2).unwrap();
+        let oxy_rating =
+            AnswerTwo::from_str_radix(oxy_rating.first().unwrap().as_str(), 2).unwrap();
 
         let mut co2_rating = self.lines.clone();
         for i in 0..co2_rating.first().unwrap().len() {
@@ -113,26 +123,14 @@ impl AdventOfCode for Puzzle {
                 break;
             }
         }
-        let co2_rating = Answer::from_str_radix(co2_rating.first().unwrap().as_str(), 2).unwrap();
+        let co2_rating =
+            AnswerTwo::from_str_radix(co2_rating.first().unwrap().as_str(), 2).unwrap();
 
-        oxy_rating * co2_rating
+        Ok(oxy_rating * co2_rating)
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 03)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 03, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -141,7 +139,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_03 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -168,7 +166,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 198);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 198);
         }
 
         // !SECTION
@@ -196,7 +194,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 230);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 230);
         }
 
         // !SECTION
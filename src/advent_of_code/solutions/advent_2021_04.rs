@@ -1,11 +1,14 @@
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -61,7 +64,7 @@ impl Bingo {
 
 /// Implement parsing a Puzzle struct from an input string
 ///
-/// TODO(macro): Add derive macro for standard implementation of FromStr
+/// Implement parsing a Puzzle struct from an input string
 impl FromStr for Puzzle {
     type Err = Error;
 
@@ -119,10 +122,16 @@ impl From<Vec<Input>> for Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         let mut boards = self.boards.clone();
         let mut winning_ball = 0;
         for ball in self.balls.clone() {
@@ -133,10 +142,10 @@ impl AdventOfCode for Puzzle {
             }
         }
         let winner = *boards.iter().filter(|b| b.is_winning()).next().unwrap();
-        winner.points() * winning_ball
+        Ok(winner.points() * winning_ball)
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         let mut boards = self
             .boards
             .clone()
@@ -149,7 +158,7 @@ impl AdventOfCode for Puzzle {
             winning_ball = ball;
             boards = boards.iter().map(|(i, b)| (*i, b.mark(ball))).collect();
 
-            if boards.len() == 1 && boards.last().unwrap().1.is_winning() {
+            if boards.len() == 1 && boards[0].1.is_winning() {
                 break;
             }
 
@@ -160,26 +169,12 @@ impl AdventOfCode for Puzzle {
                 .filter(|(_, b)| !b.is_winning())
                 .collect();
         }
-        println!("{:?}", boards);
-        let last = boards.last().unwrap().1;
-        last.points() * winning_ball
+        let (_, last) = boards.last().context("no board left to win")?;
+        Ok(last.points() * winning_ball)
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 04)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 04, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -188,7 +183,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_04 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -222,7 +217,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 4512);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 4512);
         }
 
         // !SECTION
@@ -257,7 +252,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 1924);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 1924);
         }
 
         // !SECTION
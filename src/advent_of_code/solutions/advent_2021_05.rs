@@ -1,28 +1,23 @@
 use array2d::Array2D;
 
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventInput, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
-#[derive(Debug, Clone, Copy)]
-struct Input((usize, usize), (usize, usize));
-
-impl FromStr for Input {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (left, right) = s.split_once(" -> ").context("parse a -> b")?;
-        let (x1, y1) = left.split_once(",").context("parse x1,y1")?;
-        let (x2, y2) = right.split_once(",").context("parse x2,y2")?;
-        Ok(Input(
-            (x1.parse()?, y1.parse()?),
-            (x2.parse()?, y2.parse()?),
-        ))
-    }
+#[derive(Debug, Clone, Copy, AdventInput)]
+#[advent(format = "{x1},{y1} -> {x2},{y2}")]
+struct Input {
+    x1: usize,
+    y1: usize,
+    x2: usize,
+    y2: usize,
 }
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -51,15 +46,21 @@ impl From<Vec<Input>> for Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         // First we need to get the size of the map
         let max_size = self
             .lines
             .iter()
-            .flat_map(|Input((x1, y1), (x2, y2))| vec![x1, y1, x2, y2])
+            .flat_map(|Input { x1, y1, x2, y2 }| vec![x1, y1, x2, y2])
             .max()
             .expect("could not find max size for puzzle")
             + 1;
@@ -79,7 +80,7 @@ impl AdventOfCode for Puzzle {
         // Each line is a pair of coords
         self.lines
             .iter()
-            .flat_map(|Input((x1, y1), (x2, y2))| {
+            .flat_map(|Input { x1, y1, x2, y2 }| {
                 if x1 == x2 {
                     // Vertical line
                     range(*y1, *y2).iter().map(|y| (*x1, *y)).collect_vec()
@@ -99,18 +100,18 @@ impl AdventOfCode for Puzzle {
             .rows_iter()
             .for_each(|row| debug!("{:?}", row.collect_vec()));
 
-        ocean_floor
+        Ok(ocean_floor
             .elements_row_major_iter()
             .filter(|e| **e >= 2)
-            .count()
+            .count())
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         // First we need to get the size of the map
         let max_size = self
             .lines
             .iter()
-            .flat_map(|Input((x1, y1), (x2, y2))| vec![x1, y1, x2, y2])
+            .flat_map(|Input { x1, y1, x2, y2 }| vec![x1, y1, x2, y2])
             .max()
             .expect("could not find max size for puzzle")
             + 1;
@@ -130,7 +131,7 @@ impl AdventOfCode for Puzzle {
         // Each line is a pair of coords
         self.lines
             .iter()
-            .flat_map(|Input((x1, y1), (x2, y2))| {
+            .flat_map(|Input { x1, y1, x2, y2 }| {
                 if x1 == x2 {
                     // Vertical line
                     range(*y1, *y2).iter().map(|y| (*x1, *y)).collect_vec()
@@ -154,27 +155,14 @@ impl AdventOfCode for Puzzle {
             .rows_iter()
             .for_each(|row| debug!("{:?}", row.collect_vec()));
 
-        ocean_floor
+        Ok(ocean_floor
             .elements_row_major_iter()
             .filter(|e| **e >= 2)
-            .count()
+            .count())
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 05)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 05, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -183,7 +171,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_05 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -208,7 +196,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 5);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 5);
         }
 
         // !SECTION
@@ -234,7 +222,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 12);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 12);
         }
 
         // !SECTION
@@ -1,11 +1,14 @@
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = u128;
+/// Expected output of part one
+type AnswerOne = u128;
+
+/// Expected output of part two
+type AnswerTwo = u128;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -68,38 +71,31 @@ fn advance_age(population: [u128; 9]) -> [u128; 9] {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
-        (1..=80)
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
+        Ok((1..=80)
             .fold(self.fish_population, |pop, _| advance_age(pop))
             .iter()
-            .sum()
+            .sum())
     }
 
-    fn part_two(&self) -> Self::Answer {
-        (1..=256)
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
+        Ok((1..=256)
             .fold(self.fish_population, |pop, _| advance_age(pop))
             .iter()
-            .sum()
+            .sum())
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 06)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 06, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -108,7 +104,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_06 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -119,7 +115,7 @@ mod tests {
         fn part_one_sample_1() {
             let puzzle: Puzzle = r#"3,4,3,1,2"#.parse().expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 5934);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 5934);
         }
 
         // !SECTION
@@ -131,7 +127,10 @@ mod tests {
         fn part_two_sample_1() {
             let puzzle: Puzzle = r#"3,4,3,1,2"#.parse().expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 26984457539);
+            assert_eq!(
+                puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(),
+                26984457539
+            );
         }
 
         // !SECTION
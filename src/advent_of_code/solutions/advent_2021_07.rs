@@ -1,11 +1,14 @@
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -44,17 +47,23 @@ impl From<Vec<Input>> for Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         let (min, max) = match self.crabs.iter().minmax() {
-            itertools::MinMaxResult::NoElements => todo!(),
+            itertools::MinMaxResult::NoElements => return Err(anyhow!("no crab positions")),
             itertools::MinMaxResult::OneElement(a) => (0, *a),
             itertools::MinMaxResult::MinMax(a, b) => (*a, *b),
         };
 
-        (min..=max)
+        Ok((min..=max)
             .map(|pos| {
                 let cost = self
                     .crabs
@@ -65,17 +74,17 @@ impl AdventOfCode for Puzzle {
             })
             .min_by_key(|(_, cost)| *cost)
             .unwrap_or((0, 0))
-            .1
+            .1)
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         let (min, max) = match self.crabs.iter().minmax() {
             itertools::MinMaxResult::NoElements => unreachable!(),
             itertools::MinMaxResult::OneElement(_) => unreachable!(),
             itertools::MinMaxResult::MinMax(a, b) => (*a, *b),
         };
 
-        (min..=max)
+        Ok((min..=max)
             .map(|pos| {
                 let cost = self
                     .crabs
@@ -87,24 +96,11 @@ impl AdventOfCode for Puzzle {
             })
             .min_by_key(|(_, cost)| *cost)
             .unwrap_or((0, 0))
-            .1
+            .1)
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 07)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 07, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -113,7 +109,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_07 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -126,7 +122,7 @@ mod tests {
                 .parse()
                 .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 37);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 37);
         }
 
         // !SECTION
@@ -141,7 +137,7 @@ mod tests {
                 .parse()
                 .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 168);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 168);
         }
 
         // !SECTION
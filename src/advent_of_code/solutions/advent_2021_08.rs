@@ -1,5 +1,5 @@
 use bimap::BiMap;
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
@@ -8,8 +8,11 @@ type Input = String;
 #[derive(Debug, Clone)]
 struct Instruction(Vec<String>, Vec<String>);
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -52,11 +55,18 @@ impl From<Vec<Input>> for Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
-        self.lines
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
+        Ok(self
+            .lines
             .iter()
             .flat_map(|i| i.1.iter())
             .filter_map(|d| match d.len() {
@@ -66,10 +76,10 @@ impl AdventOfCode for Puzzle {
                 7 => Some(8),
                 _ => None,
             })
-            .count()
+            .count())
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         let first_guess = |d: &String| match d.len() {
             2 => Some(1),
             3 => Some(7),
@@ -143,24 +153,11 @@ impl AdventOfCode for Puzzle {
                 .fold(0, |acc, d| 10 * acc + d)
         };
 
-        self.lines.iter().map(|i| compute(i.clone())).sum()
+        Ok(self.lines.iter().map(|i| compute(i.clone())).sum())
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 08)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 08, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -169,7 +166,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_08 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -194,7 +191,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 26);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 26);
         }
 
         // !SECTION
@@ -211,7 +208,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 5353);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 5353);
         }
 
         /// Solver PART ${1|one,two|/}
@@ -233,7 +230,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 61229);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 61229);
         }
 
         // !SECTION
@@ -1,14 +1,17 @@
 use std::collections::HashSet;
 
 use array2d::Array2D;
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[allow(dead_code)]
@@ -52,10 +55,16 @@ impl From<Vec<Input>> for Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         let num_rows = self.heightmap.num_rows() as isize;
         let num_columns = self.heightmap.num_columns() as isize;
 
@@ -89,14 +98,14 @@ impl AdventOfCode for Puzzle {
             })
             .collect_vec();
 
-        low_points
+        Ok(low_points
             .iter()
             .map(|(x, y)| 1 + self.heightmap.get(*x, *y).unwrap())
-            .sum()
+            .sum())
     }
 
     #[allow(unused_variables)]
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
@@ -165,30 +174,17 @@ impl AdventOfCode for Puzzle {
             basins.iter().map(|b| b.len()).sorted().rev().collect_vec()
         );
 
-        basins
+        Ok(basins
             .iter()
             .map(|b| b.len())
             .sorted()
             .rev()
             .take(3)
-            .product()
+            .product())
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 09)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 09, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -197,7 +193,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_09 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -217,7 +213,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 15);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 15);
         }
 
         // !SECTION
@@ -238,7 +234,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 1134);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 1134);
         }
 
         // !SECTION
@@ -1,12 +1,15 @@
 use bimap::BiMap;
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -98,10 +101,16 @@ fn parse(chunk: String) -> ChunkToken {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         #[cfg(test)]
         debug!("Puzzle: \n{}", self.instructions.iter().join("\n"));
 
@@ -114,7 +123,8 @@ impl AdventOfCode for Puzzle {
                 .collect_vec()
         );
 
-        self.instructions
+        Ok(self
+            .instructions
             .iter()
             .map(|l| parse(l.to_string()))
             .filter_map(|r| match r {
@@ -127,10 +137,10 @@ impl AdventOfCode for Puzzle {
                 }),
                 _ => None,
             })
-            .sum()
+            .sum())
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         #[cfg(test)]
         debug!("Puzzle: \n{}", self.instructions.iter().join("\n"));
 
@@ -163,24 +173,11 @@ impl AdventOfCode for Puzzle {
             .sorted()
             .collect_vec();
 
-        scores[scores.len() / 2]
+        Ok(scores[scores.len() / 2])
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 10)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 10, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -189,7 +186,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_10 {
-        use crate::{parse, ChunkToken, Puzzle};
+        use super::super::{parse, ChunkToken, Puzzle};
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -214,7 +211,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 26397);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 26397);
         }
 
         #[test]
@@ -264,7 +261,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 288957);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 288957);
         }
 
         /// Solver PART two
@@ -277,7 +274,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 288957);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 288957);
         }
 
         #[test]
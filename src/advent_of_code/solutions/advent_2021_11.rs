@@ -1,12 +1,15 @@
 use array2d::Array2D;
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone, PartialEq)]
@@ -22,6 +25,15 @@ struct Puzzle {
     cavern: Array2D<Octopus>,
 }
 
+/// Outcome of some steps
+#[derive(Debug, Clone)]
+struct Steps {
+    cavern: Array2D<Octopus>,
+    flashes: usize,
+    /// First step at which every octopus flashed at once
+    synchronized: Option<usize>,
+}
+
 /// Implement parsing a Puzzle struct from an input string
 impl FromStr for Puzzle {
     type Err = Error;
@@ -37,16 +49,15 @@ impl FromStr for Puzzle {
 /// Collect a Vec<Input> input a structured Puzzle
 impl From<Vec<Input>> for Puzzle {
     fn from(input: Vec<Input>) -> Self {
-        Self {
-            cavern: Array2D::from_iter_row_major(
-                input
-                    .iter()
-                    .flat_map(|l| l.trim().chars())
-                    .map(|o| Octopus::Charging(o.to_string().parse().unwrap())),
-                input.len(),
-                input.first().unwrap().len(),
-            ),
-        }
+        let cavern = Array2D::from_iter_row_major(
+            input
+                .iter()
+                .flat_map(|l| l.trim().chars())
+                .map(|o| Octopus::Charging(o.to_string().parse().unwrap())),
+            input.len(),
+            input.first().unwrap().len(),
+        );
+        Self { cavern }
     }
 }
 
@@ -129,6 +140,35 @@ fn tick(cavern: Array2D<Octopus>) -> (Array2D<Octopus>, usize) {
     (cavern, flashes)
 }
 
+/// Whether every octopus flashed at once
+fn synchronized(cavern: &Array2D<Octopus>) -> bool {
+    cavern
+        .elements_row_major_iter()
+        .tuple_windows()
+        .all(|(prev, item)| prev == item)
+}
+
+/// Run a number of steps
+fn steps(cavern: Array2D<Octopus>, count: usize) -> Steps {
+    (1..=count).fold(
+        Steps {
+            cavern,
+            flashes: 0,
+            synchronized: None,
+        },
+        |past, step| {
+            let (cavern, flashes) = tick(past.cavern);
+            Steps {
+                synchronized: past
+                    .synchronized
+                    .or_else(|| synchronized(&cavern).then(|| step)),
+                cavern,
+                flashes: past.flashes + flashes,
+            }
+        },
+    )
+}
+
 #[allow(dead_code)]
 fn repr(cavern: Array2D<Octopus>) -> String {
     cavern
@@ -147,55 +187,44 @@ fn repr(cavern: Array2D<Octopus>) -> String {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    /// The steps of part one, part two goes on from there
+    type State = Steps;
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(steps(self.cavern.clone(), 100))
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, first_steps: &Self::State) -> Result<Self::AnswerOne> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
-        (1..=100)
-            .fold((self.cavern.clone(), 0), |(cavern, past_flashes), _| {
-                let (cavern, flashes) = tick(cavern);
-                (cavern, past_flashes + flashes)
-            })
-            .1
+        Ok(first_steps.flashes)
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, first_steps: &Self::State) -> Result<Self::AnswerTwo> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
-        let mut cavern = self.cavern.clone();
-        let mut step = 0;
+
+        if let Some(step) = first_steps.synchronized {
+            return Ok(step);
+        }
+        let mut cavern = first_steps.cavern.clone();
+        let mut step = 100;
         loop {
             let (next, _) = tick(cavern);
             step += 1;
-            if next
-                .elements_row_major_iter()
-                .tuple_windows()
-                .all(|(prev, item)| prev == item)
-            {
-                break step;
+            if synchronized(&next) {
+                break Ok(step);
             }
             cavern = next;
         }
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 11)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 11, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -204,7 +233,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_11 {
-        use crate::{tick, Octopus, Puzzle};
+        use super::super::{tick, Octopus, Puzzle};
         use array2d::Array2D;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
@@ -230,13 +259,35 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 1656);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 1656);
         }
 
         // !SECTION
 
         // SECTION: PART TWO
 
+        /// Solver PART two
+        /// Test Case: sample_1
+        #[test]
+        fn part_two_sample_1() {
+            let puzzle: Puzzle = r#"
+                5483143223
+                2745854711
+                5264556173
+                6141336146
+                6357385478
+                4167524645
+                2176841721
+                6882881134
+                4846848554
+                5283751526
+            "#
+            .parse()
+            .expect("failed to parse input string");
+
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 195);
+        }
+
         // !SECTION
     }
 }
@@ -1,13 +1,16 @@
 use std::collections::{HashMap, HashSet};
 
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -51,122 +54,83 @@ impl From<Vec<Input>> for Puzzle {
     }
 }
 
-impl Puzzle {
-    fn part_one_paths(&self) -> HashSet<String> {
-        #[cfg(test)]
-        debug!("Puzzle: {:?}", self);
+/// Paths without a small cave visited twice, marked by the joker
+fn part_one_paths(paths: &HashSet<String>) -> HashSet<String> {
+    paths.iter().filter(|p| !p.contains('*')).cloned().collect()
+}
 
-        let rooms = self.rooms.clone();
+/// Every path of part two, visiting at most one small cave twice
+fn explore(rooms: &HashMap<String, HashSet<String>>) -> HashSet<String> {
+    #[cfg(test)]
+    debug!("Rooms: {:?}", rooms);
 
-        let mut paths: HashSet<String> = HashSet::new();
-        let mut visiting: Vec<(String, Vec<String>)> =
-            vec![("start".to_string(), vec!["start".to_string()])];
+    let mut paths: HashSet<String> = HashSet::new();
+    let mut visiting: Vec<(String, Vec<String>)> =
+        vec![("start".to_string(), vec!["start".to_string()])];
 
-        while !visiting.is_empty() {
-            let (current_room, path) = visiting.pop().unwrap();
+    while !visiting.is_empty() {
+        let (current_room, path) = visiting.pop().unwrap();
 
-            for next_room in rooms.get(&current_room).unwrap() {
-                // Impasse (we already visited the next room and it's a small one)
-                let visited = path.contains(next_room);
-                let is_small = next_room == &next_room.to_lowercase();
-                if is_small && visited {
-                    continue;
-                };
+        for next_room in rooms.get(&current_room).unwrap() {
+            let visited = path.contains(next_room) || path.contains(&["*", next_room].concat());
+            let is_small = next_room == &next_room.to_lowercase();
+            let joker_used = path.iter().any(|room| room.starts_with("*"));
 
-                let mut path = path.clone();
+            let mut path = path.clone();
+            if next_room == "start" {
+                continue;
+            }
+            // Impasse (we already visited the next room and it's a small one)
+            else if is_small && visited && joker_used {
+                continue;
+            } else if is_small && visited {
+                path.push(["*", next_room].concat());
+            } else {
                 path.push(next_room.clone().to_string());
-
-                if next_room == "end" {
-                    // End of path
-                    paths.insert(path.iter().join(","));
-                } else {
-                    // Continue exploration
-                    visiting.push((next_room.clone(), path));
-                }
+            };
+
+            if next_room == "end" {
+                // End of path
+                paths.insert(path.iter().join(","));
+            } else {
+                // Continue exploration
+                visiting.push((next_room.clone(), path));
             }
         }
-        paths
     }
-    fn part_two_paths(&self) -> HashSet<String> {
-        #[cfg(test)]
-        debug!("Puzzle: {:?}", self);
-
-        let rooms = self.rooms.clone();
-
-        let mut paths: HashSet<String> = HashSet::new();
-        let mut visiting: Vec<(String, Vec<String>)> =
-            vec![("start".to_string(), vec!["start".to_string()])];
-
-        while !visiting.is_empty() {
-            let (current_room, path) = visiting.pop().unwrap();
-
-            for next_room in rooms.get(&current_room).unwrap() {
-                let visited = path.contains(next_room) || path.contains(&["*", next_room].concat());
-                let is_small = next_room == &next_room.to_lowercase();
-                let joker_used = path.iter().any(|room| room.starts_with("*"));
-
-                let mut path = path.clone();
-                if next_room == "start" {
-                    continue;
-                }
-                // Impasse (we already visited the next room and it's a small one)
-                else if is_small && visited && joker_used {
-                    continue;
-                } else if is_small && visited {
-                    path.push(["*", next_room].concat());
-                } else {
-                    path.push(next_room.clone().to_string());
-                };
 
-                if next_room == "end" {
-                    // End of path
-                    paths.insert(path.iter().join(","));
-                } else {
-                    // Continue exploration
-                    visiting.push((next_room.clone(), path));
-                }
-            }
+    #[cfg(test)]
+    if paths.len() < 40 {
+        for p in paths.clone() {
+            debug!("  > {}", p);
         }
-
-        #[cfg(test)]
-        if paths.len() < 40 {
-            for p in paths.clone() {
-                debug!("  > {}", p);
-            }
-        };
-        paths
-    }
+    };
+    paths
 }
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    /// Every path of part two, part one keeps those without a joker
+    type State = HashSet<String>;
 
-    fn part_one(&self) -> Self::Answer {
-        self.part_one_paths().len()
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(explore(&self.rooms))
     }
 
-    fn part_two(&self) -> Self::Answer {
-        self.part_two_paths().len()
+    fn part_one(&self, paths: &Self::State) -> Result<Self::AnswerOne> {
+        Ok(part_one_paths(paths).len())
     }
-}
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 12)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
+    fn part_two(&self, paths: &Self::State) -> Result<Self::AnswerTwo> {
+        Ok(paths.len())
+    }
 }
 
+puzzling::advent_solution!(2021, 12, Puzzle);
+
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
 #[cfg(feature = "completed")]
@@ -176,7 +140,7 @@ mod tests {
     mod advent_2021_12 {
         use std::collections::HashSet;
 
-        use crate::Puzzle;
+        use super::super::{part_one_paths, Puzzle};
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -198,9 +162,9 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 10);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 10);
             assert_eq!(
-                puzzle.part_one_paths(),
+                part_one_paths(&puzzle.prepare().unwrap()),
                 HashSet::from([
                     "start,A,b,A,c,A,end".to_string(),
                     "start,A,b,A,end".to_string(),
@@ -235,7 +199,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 19);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 19);
         }
 
         /// Solver PART one
@@ -265,7 +229,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 226);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 226);
         }
 
         // !SECTION
@@ -288,7 +252,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 36);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 36);
         }
 
         /// Solver PART two
@@ -310,7 +274,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 103);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 103);
         }
 
         /// Solver PART two
@@ -340,7 +304,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 3509);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 3509);
         }
 
         // !SECTION
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+use puzzling::advent_of_code::{letters, parsing, AdventInput, AdventOfCode};
+use puzzling::prelude::*;
+
+/// Input type for each line
+type Input = Line;
+
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two: the letters drawn by the holes
+type AnswerTwo = String;
+
+/// Representation of a complete puzzle
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+struct Puzzle {
+    holes: HashSet<Hole>,
+    folds: Vec<Fold>,
+}
+
+/// Implement parsing a Puzzle struct from an input string
+impl FromStr for Puzzle {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // Standard parsing of input
+        parsing::lines::<Input>(s)
+            // Creation using the From<Vec<Input>> input
+            .map(|lines: Vec<Input>| -> Self { lines.into() })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, AdventInput)]
+#[advent(format = "{0},{1}")]
+struct Hole(isize, isize);
+
+impl Hole {
+    fn fold(&self, fold: Fold) -> Option<Self> {
+        let (pos, line) = match fold {
+            Fold::X(line) => (self.0, line),
+            Fold::Y(line) => (self.1, line),
+        };
+        if pos == line {
+            return None;
+        }
+
+        let pos = if pos < line { pos } else { 2 * line - pos };
+
+        match fold {
+            Fold::X(_) => Some(Hole(pos, self.1)),
+            Fold::Y(_) => Some(Hole(self.0, pos)),
+        }
+    }
+}
+
+/// Folding instruction, along a vertical (x) or horizontal (y) line
+#[derive(Debug, Clone, Copy, AdventInput)]
+enum Fold {
+    #[advent(format = "fold along x={0}")]
+    X(isize),
+    #[advent(format = "fold along y={0}")]
+    Y(isize),
+}
+
+/// A line of the input: the holes, then a blank line, then the folding instructions
+#[derive(Debug, Clone, Copy, AdventInput)]
+enum Line {
+    #[advent(format = "{0}")]
+    Hole(Hole),
+    #[advent(format = "{0}")]
+    Fold(Fold),
+    #[advent(format = "")]
+    Blank,
+}
+
+/// Collect a Vec<Input> input a structured Puzzle
+impl From<Vec<Input>> for Puzzle {
+    fn from(input: Vec<Input>) -> Self {
+        let holes = input
+            .iter()
+            .filter_map(|line| match line {
+                Line::Hole(hole) => Some(*hole),
+                _ => None,
+            })
+            .collect();
+
+        let folds = input
+            .iter()
+            .filter_map(|line| match line {
+                Line::Fold(fold) => Some(*fold),
+                _ => None,
+            })
+            .collect();
+
+        Self { holes, folds }
+    }
+}
+
+impl Puzzle {
+    fn fold(&self) -> Result<Self> {
+        let fold = self
+            .folds
+            .first()
+            .context("no folding instruction")?
+            .clone();
+
+        let holes = self
+            .holes
+            .iter()
+            .cloned()
+            .filter_map(|h| h.fold(fold))
+            .collect();
+
+        let folds = self.folds.iter().skip(1).cloned().collect_vec();
+        Ok(Self { holes, folds })
+    }
+}
+
+impl AdventOfCode for Puzzle {
+    type Input = Input;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
+    type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
+
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
+        #[cfg(test)]
+        debug!("Puzzle: {:?}", self);
+
+        Ok(self.fold()?.holes.len())
+    }
+
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
+        #[cfg(test)]
+        debug!("Puzzle: {:?}", self);
+
+        let mut paper = self.clone();
+        while !paper.folds.is_empty() {
+            paper = paper.fold()?
+        }
+
+        let dots = paper.holes.iter().map(|Hole(x, y)| (*x, *y)).collect();
+        let drawing = letters::draw(&dots);
+        // The drawing is in the error, to be read by eye when some letters are unknown
+        letters::read(&drawing)
+            .ok_or_else(|| anyhow!("unknown letters in the drawing:\n{}", drawing.join("\n")))
+    }
+}
+
+puzzling::advent_solution!(2021, 13, Puzzle);
+
+#[cfg(test)]
+#[cfg(feature = "advent_2021")]
+#[cfg(feature = "completed")]
+mod tests {
+
+    #[allow(unused_imports)]
+    mod advent_2021_13 {
+        use super::super::Puzzle;
+        use puzzling::{advent_of_code::AdventOfCode, prelude::*};
+        use test_log::test;
+
+        // SECTION: PART ONE
+
+        /// Solver PART one
+        /// Test Case: sample_1
+        #[test]
+        fn part_one_sample_1() {
+            let puzzle: Puzzle = r#"
+            6,10
+            0,14
+            9,10
+            0,3
+            10,4
+            4,11
+            6,0
+            6,12
+            4,1
+            0,13
+            10,12
+            3,4
+            3,0
+            8,4
+            1,10
+            2,14
+            8,10
+            9,0
+
+            fold along y=7
+            fold along x=5
+            "#
+            .parse()
+            .expect("failed to parse input string");
+
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 17);
+        }
+
+        #[test]
+        fn part_one_sample_2() {
+            let puzzle: Puzzle = r#"
+            6,10
+            0,14
+            9,10
+            0,3
+            10,4
+            4,11
+            6,0
+            6,12
+            4,1
+            0,13
+            10,12
+            3,4
+            3,0
+            8,4
+            1,10
+            2,14
+            8,10
+            9,0
+
+            fold along x=5
+            fold along y=7
+            "#
+            .parse()
+            .expect("failed to parse input string");
+
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 17);
+        }
+
+        #[test]
+        fn parse_invalid_instruction() {
+            let error = "6,10\n\nfold along z=3".parse::<Puzzle>().unwrap_err();
+            assert!(format!("{:#}", error).contains("fold along z=3"));
+        }
+
+        // !SECTION
+
+        // SECTION: PART TWO
+
+        /// Solver PART two
+        /// Test Case: sample_1
+        #[test]
+        fn part_two_sample_1() {
+            let puzzle: Puzzle = r#"
+            6,10
+            0,14
+            9,10
+            0,3
+            10,4
+            4,11
+            6,0
+            6,12
+            4,1
+            0,13
+            10,12
+            3,4
+            3,0
+            8,4
+            1,10
+            2,14
+            8,10
+            9,0
+
+            fold along y=7
+            fold along x=5
+            "#
+            .parse()
+            .expect("failed to parse input string");
+
+            // The sample draws a square, which is not a letter
+            assert_eq!(
+                puzzle
+                    .part_two(&puzzle.prepare().unwrap())
+                    .unwrap_err()
+                    .to_string(),
+                "unknown letters in the drawing:\n#####\n#...#\n#...#\n#...#\n#####"
+            );
+        }
+
+        // !SECTION
+    }
+}
@@ -1,15 +1,17 @@
 #![allow(dead_code, unused_variables)]
 use std::collections::HashMap;
 
-use itertools::MinMaxResult;
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -77,34 +79,42 @@ impl Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
         let polymer = self.starting_polymer.clone();
 
-        if let MinMaxResult::MinMax(min, max) = (0..10)
+        let (min, max) = (0..10)
             .fold(polymer, |polymer, _| self.polymerize(polymer))
             .chars()
             .counts()
             .into_iter()
             .minmax_by_key(|&(_, count)| count)
-        {
-            debug!("Minmax: {:?} > {:?}", max, min);
-            max.1 - min.1
-        } else {
-            0
-        }
+            .into_option()
+            .context("empty polymer")?;
+        debug!("Minmax: {:?} > {:?}", max, min);
+        Ok(max.1 - min.1)
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
-        let start = self.starting_polymer.chars().nth(0).unwrap();
+        let start = self
+            .starting_polymer
+            .chars()
+            .nth(0)
+            .context("empty starting polymer")?;
         let polymer: HashMap<(char, char), usize> = self
             .starting_polymer
             .clone()
@@ -141,28 +151,16 @@ impl AdventOfCode for Puzzle {
         );
 
         debug!("Elements: {:?}", elements);
-        match elements.into_iter().minmax_by_key(|(_, count)| *count) {
-            MinMaxResult::NoElements => todo!(),
-            MinMaxResult::OneElement(_) => todo!(),
-            MinMaxResult::MinMax((_, a), (_, b)) => b - a,
-        }
+        let ((_, least), (_, most)) = elements
+            .into_iter()
+            .minmax_by_key(|(_, count)| *count)
+            .into_option()
+            .context("empty polymer")?;
+        Ok(most - least)
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 14)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 14, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -171,7 +169,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_14 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -204,7 +202,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 1588);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 1588);
         }
 
         // !SECTION
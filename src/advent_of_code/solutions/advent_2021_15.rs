@@ -1,13 +1,16 @@
 #![allow(dead_code, unused_variables)]
 use array2d::Array2D;
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part one
+type AnswerOne = usize;
+
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -47,7 +50,6 @@ impl From<Vec<Input>> for Puzzle {
 
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, HashSet};
-use std::time::Instant;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 struct Point {
@@ -196,24 +198,31 @@ impl Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         let path = Puzzle::safest_path(
             self.map.clone(),
             (0, 0),
             (self.map.num_rows() - 1, self.map.num_columns() - 1),
         )
-        .unwrap();
+        .context("no path to the bottom right corner")?;
 
-        path.iter()
+        Ok(path
+            .iter()
             .skip(1)
             .map(|&(x, y)| self.map.get(x, y).unwrap())
-            .sum::<usize>()
+            .sum::<usize>())
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         let num_rows = self.map.num_rows();
         let num_columns = self.map.num_columns();
 
@@ -240,45 +249,20 @@ impl AdventOfCode for Puzzle {
             (0, 0),
             (larger_map.num_rows() - 1, larger_map.num_columns() - 1),
         )
-        .unwrap();
+        .context("no path to the bottom right corner of the full map")?;
 
         #[cfg(test)]
         debug!("\n{}", Puzzle::show_path(larger_map.clone(), path.clone()));
 
-        path.iter()
+        Ok(path
+            .iter()
             .skip(1)
             .map(|&(x, y)| larger_map.get(x, y).unwrap())
-            .sum::<usize>()
+            .sum::<usize>())
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 15)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    let timeit = Instant::now();
-    let part_one = puzzle.part_one();
-    println!(
-        "Part 1 ({:.04}s): {}",
-        timeit.elapsed().as_secs_f64(),
-        part_one,
-    );
-
-    let timeit = Instant::now();
-    let part_two = puzzle.part_two();
-    println!(
-        "Part 2 ({:.04}s): {}",
-        timeit.elapsed().as_secs_f64(),
-        part_two,
-    );
-}
+puzzling::advent_solution!(2021, 15, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -287,7 +271,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_15 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -312,7 +296,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 40);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 40);
         }
 
         // !SECTION
@@ -338,7 +322,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_two(), 315);
+            assert_eq!(puzzle.part_two(&puzzle.prepare().unwrap()).unwrap(), 315);
         }
 
         // !SECTION
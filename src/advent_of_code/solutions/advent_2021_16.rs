@@ -1,12 +1,15 @@
 #![allow(dead_code, unused_variables)]
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 use std::collections::VecDeque;
 use std::str;
 /// Input type for each line
 type Input = String;
-/// Expected output
-type Answer = u128;
+/// Expected output of part one
+type AnswerOne = u128;
+
+/// Expected output of part two
+type AnswerTwo = u128;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -178,10 +181,16 @@ impl BuoyancyPacket {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         #[cfg(test)]
         debug!("Packet: \n{}", Puzzle::format_packet(&self.packet));
 
@@ -202,10 +211,10 @@ impl AdventOfCode for Puzzle {
             }
         }
 
-        walk(&packet)
+        Ok(walk(&packet))
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
@@ -213,20 +222,7 @@ impl AdventOfCode for Puzzle {
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 16)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 16, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -235,7 +231,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_16 {
-        use crate::{BuoyancyPacket, BuoyancyTape, Puzzle};
+        use super::super::{BuoyancyPacket, BuoyancyTape, Puzzle};
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -360,7 +356,7 @@ mod tests {
                 }
             );
 
-            assert_eq!(puzzle.part_one(), 16);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 16);
         }
 
         /// Solver PART one
@@ -414,7 +410,7 @@ mod tests {
                 }
             );
 
-            assert_eq!(puzzle.part_one(), 12);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 12);
         }
 
         /// Solver PART one
@@ -427,7 +423,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 23);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 23);
         }
 
         /// Solver PART one
@@ -440,7 +436,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 31);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 31);
         }
 
         // !SECTION
@@ -1,25 +1,20 @@
 #![allow(dead_code, unused_variables)]
-#![allow(incomplete_features)]
-#![feature(
-    never_type,
-    generators,
-    generator_trait,
-    type_alias_impl_trait,
-    let_chains
-)]
 
 use std::fmt::{Display, Formatter};
 use std::ops::{Generator, GeneratorState};
 use std::pin::Pin;
 
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventOfCode};
 use puzzling::prelude::*;
 
 /// Input type for each line
 type Input = String;
 
-/// Expected output
-type Answer = isize;
+/// Expected output of part one
+type AnswerOne = isize;
+
+/// Expected output of part two
+type AnswerTwo = isize;
 
 type Trajectory = impl Generator<Yield = (isize, isize), Return = !> + Unpin;
 
@@ -135,25 +130,31 @@ impl Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, _: &Self::State) -> Result<Self::AnswerOne> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
         let (mut vx, mut vy) = (0, 1);
-        return 0;
+        return Ok(0);
         unreachable!();
         loop {
             let mut trajectory = Self::launch_pod(0, 1);
             if let Some(trajectory) = self.target_area.aim_with(&mut trajectory) {
-                break trajectory.iter().cloned().map(|(_, y)| y).max().unwrap();
+                break Ok(trajectory.iter().cloned().map(|(_, y)| y).max().unwrap());
             }
         }
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, _: &Self::State) -> Result<Self::AnswerTwo> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
@@ -161,20 +162,7 @@ impl AdventOfCode for Puzzle {
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(2021, 17)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(2021, 17, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_2021")]
@@ -183,7 +171,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_2021_17 {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 
@@ -199,7 +187,7 @@ mod tests {
             .parse()
             .expect("failed to parse input string");
 
-            assert_eq!(puzzle.part_one(), 45);
+            assert_eq!(puzzle.part_one(&puzzle.prepare().unwrap()).unwrap(), 45);
         }
 
         // !SECTION
@@ -0,0 +1,5 @@
+//! Solution of each day, registered with [advent_solution](crate::advent_solution)
+//!
+//! Every `advent_YYYY_DD.rs` file of this directory is a module, declared by `build.rs` behind
+//! its `advent_YYYY` feature: adding a day only takes creating its file (see `advent prepare`).
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
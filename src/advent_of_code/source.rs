@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+
+use super::cache::Cache;
+use super::challenge::{fetch_daily_challenge, ChallengeError};
+use super::session::Session;
+use super::vault::Vault;
+use crate::config::Settings;
+use crate::prelude::*;
+
+/// Somewhere daily challenges can be read from
+pub trait ChallengeSource {
+    fn challenge(&self, year: usize, day: usize) -> Result<String, ChallengeError>;
+}
+
+/// The live website, behind the local cache
+#[derive(Debug, Clone)]
+pub struct HttpSource {
+    pub session: Session,
+    pub cache: Cache,
+}
+
+impl HttpSource {
+    pub fn from_settings() -> Result<Self> {
+        Ok(Self {
+            session: Session::from_settings()?,
+            cache: Cache::from_settings(),
+        })
+    }
+
+    fn cached(&self, year: usize, day: usize) -> Result<Option<String>, ChallengeError> {
+        let path = Cache::challenge_path(year, day);
+        if !self.cache.root().join(&path).exists() {
+            return Ok(None);
+        }
+
+        match self.cache.read(&path) {
+            Ok(challenge) if !challenge.is_empty() => {
+                info!("Cache hit");
+                Ok(Some(challenge))
+            }
+            Ok(_) | Err(_) => Err(ChallengeError::CacheCorrupted(self.cache.root().join(path))),
+        }
+    }
+}
+
+impl ChallengeSource for HttpSource {
+    fn challenge(&self, year: usize, day: usize) -> Result<String, ChallengeError> {
+        if let Some(challenge) = self.cached(year, day)? {
+            return Ok(challenge);
+        }
+        info!("Cache miss, fetching fresh challenge");
+
+        let challenge = fetch_daily_challenge(&self.session, year, day)?;
+        info!("Challenge fetched: Content-Length: {}", challenge.len());
+
+        info!("Writing challenge to cache");
+        if let Err(e) = self
+            .cache
+            .write(Cache::challenge_path(year, day), &challenge)
+        {
+            warn!("Could not save challenge to cache: {:?}", e);
+        }
+
+        Ok(challenge)
+    }
+}
+
+/// A directory of input files laid out as `<year>/<day>.txt`
+#[derive(Debug, Clone)]
+pub struct DirectorySource {
+    pub root: PathBuf,
+}
+
+impl ChallengeSource for DirectorySource {
+    fn challenge(&self, year: usize, day: usize) -> Result<String, ChallengeError> {
+        let path = self.root.join(Cache::challenge_path(year, day));
+        if !path.exists() {
+            return Err(ChallengeError::Missing {
+                year,
+                day,
+                origin: self.root.display().to_string(),
+            });
+        }
+        info!("Reading challenge from: {:?}", path);
+        Ok(std::fs::read_to_string(path)?)
+    }
+}
+
+/// A single input file, whatever the day asked for
+///
+/// Only meant for a single day: it is refused as the `input` setting, which applies to every day.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    pub path: PathBuf,
+}
+
+impl ChallengeSource for FileSource {
+    fn challenge(&self, _year: usize, _day: usize) -> Result<String, ChallengeError> {
+        info!("Reading challenge from: {:?}", self.path);
+        Ok(std::fs::read_to_string(&self.path)?)
+    }
+}
+
+/// The standard input of the process
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdinSource;
+
+impl ChallengeSource for StdinSource {
+    fn challenge(&self, _year: usize, _day: usize) -> Result<String, ChallengeError> {
+        info!("Reading challenge from standard input");
+        let mut challenge = String::new();
+        std::io::stdin().read_to_string(&mut challenge)?;
+        Ok(challenge)
+    }
+}
+
+/// Inputs compiled into the binary
+///
+/// ```ignore
+/// let source = EmbeddedSource::default().with(2021, 1, include_str!("../inputs/2021/1.txt"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EmbeddedSource {
+    inputs: HashMap<(usize, usize), &'static str>,
+}
+
+impl EmbeddedSource {
+    pub fn with(mut self, year: usize, day: usize, input: &'static str) -> Self {
+        self.inputs.insert((year, day), input);
+        self
+    }
+}
+
+impl ChallengeSource for EmbeddedSource {
+    fn challenge(&self, year: usize, day: usize) -> Result<String, ChallengeError> {
+        self.inputs
+            .get(&(year, day))
+            .map(|input| input.to_string())
+            .ok_or(ChallengeError::Missing {
+                year,
+                day,
+                origin: "embedded inputs".into(),
+            })
+    }
+}
+
+/// Try a source, then fallback on another one when it has no input for the day
+pub struct FallbackSource {
+    pub primary: Box<dyn ChallengeSource>,
+    pub fallback: Box<dyn ChallengeSource>,
+}
+
+impl ChallengeSource for FallbackSource {
+    fn challenge(&self, year: usize, day: usize) -> Result<String, ChallengeError> {
+        match self.primary.challenge(year, day) {
+            Err(ChallengeError::Missing { .. }) => self.fallback.challenge(year, day),
+            result => result,
+        }
+    }
+}
+
+/// Build a source from its textual description
+///
+/// - `-`: standard input
+/// - `http` or empty: the website, behind the [Vault] when a key is configured
+/// - path to a directory: [DirectorySource]
+/// - path to a file: [FileSource]
+pub fn source_from_spec(spec: &str) -> Result<Box<dyn ChallengeSource>, ChallengeError> {
+    Ok(match spec.trim() {
+        "-" => Box::new(StdinSource),
+        "" | "http" => {
+            let http = Box::new(HttpSource::from_settings().map_err(ChallengeError::Settings)?);
+            match Vault::from_settings().map_err(ChallengeError::Settings)? {
+                Some(vault) => Box::new(FallbackSource {
+                    primary: Box::new(vault),
+                    fallback: http,
+                }),
+                None => http,
+            }
+        }
+        path if PathBuf::from(path).is_dir() => Box::new(DirectorySource { root: path.into() }),
+        path => Box::new(FileSource { path: path.into() }),
+    })
+}
+
+/// Whether a spec reads the same input whatever the day: a file or the standard input
+pub fn is_single_input(spec: &str) -> bool {
+    let spec = spec.trim();
+    spec == "-" || PathBuf::from(spec).is_file()
+}
+
+/// Source described by `spec` (usually an `--input` flag), or the `input` setting, defaulting
+/// to the website
+pub fn configured_source(spec: Option<&str>) -> Result<Box<dyn ChallengeSource>, ChallengeError> {
+    if let Some(spec) = spec {
+        return source_from_spec(spec);
+    }
+
+    match &Settings::get().advent_of_code.input {
+        Some(spec) if is_single_input(spec) => Err(ChallengeError::InvalidSource(format!(
+            "the input setting applies to every day, {} is a single input",
+            spec
+        ))),
+        Some(spec) => source_from_spec(spec),
+        None => source_from_spec("http"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::testing::scratch;
+    use test_log::test;
+
+    #[test]
+    fn directory_source() {
+        let root = scratch("directory_source");
+        std::fs::create_dir_all(root.join("2021")).unwrap();
+        std::fs::write(root.join("2021").join("1.txt"), "199").unwrap();
+        let source = DirectorySource { root };
+
+        assert_eq!(source.challenge(2021, 1).unwrap(), "199");
+        assert!(matches!(
+            source.challenge(2021, 2),
+            Err(ChallengeError::Missing {
+                year: 2021,
+                day: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn sources_from_spec() {
+        let root = scratch("sources_from_spec");
+        std::fs::create_dir_all(root.join("2021")).unwrap();
+        std::fs::write(root.join("2021").join("1.txt"), "199").unwrap();
+        let file = root.join("2021").join("1.txt");
+
+        let from_directory = source_from_spec(root.to_str().unwrap()).unwrap();
+        assert_eq!(from_directory.challenge(2021, 1).unwrap(), "199");
+        assert!(from_directory.challenge(2021, 2).is_err());
+
+        let from_file = source_from_spec(file.to_str().unwrap()).unwrap();
+        assert_eq!(from_file.challenge(2021, 2).unwrap(), "199");
+
+        assert!(is_single_input("-"));
+        assert!(is_single_input(file.to_str().unwrap()));
+        assert!(!is_single_input(root.to_str().unwrap()));
+        assert!(!is_single_input("http"));
+    }
+
+    #[test]
+    fn fallback_only_on_missing_inputs() {
+        let root = scratch("fallback_only_on_missing_inputs");
+        // A directory where the input should be: not missing, but unreadable
+        std::fs::create_dir_all(root.join("2021").join("3.txt")).unwrap();
+
+        let source = FallbackSource {
+            primary: Box::new(DirectorySource { root }),
+            fallback: Box::new(
+                EmbeddedSource::default()
+                    .with(2021, 2, "forward 5")
+                    .with(2021, 3, "00100"),
+            ),
+        };
+
+        assert_eq!(source.challenge(2021, 2).unwrap(), "forward 5");
+        assert!(matches!(
+            source.challenge(2021, 3),
+            Err(ChallengeError::Io(_))
+        ));
+        assert!(matches!(
+            source.challenge(2021, 4),
+            Err(ChallengeError::Missing { .. })
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use regex::Regex;
+use reqwest::StatusCode;
+use serde_derive::{Deserialize, Serialize};
+
+use super::ledger::AnswerLedger;
+use super::puzzle::Part;
+use super::session::Session;
+use crate::prelude::*;
+
+/// Outcome of an answer submission, as reported by the website
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    AlreadySolved,
+    RateLimited(Duration),
+}
+
+impl Verdict {
+    /// Whether the website actually judged the answer
+    pub fn is_judged(&self) -> bool {
+        !matches!(self, Verdict::AlreadySolved | Verdict::RateLimited(_))
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "wrong (too high)"),
+            Verdict::TooLow => write!(f, "wrong (too low)"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::RateLimited(wait) => write!(f, "rate limited ({}s left)", wait.as_secs()),
+        }
+    }
+}
+
+/// Submit an answer using the session configured in `puzzling.toml`
+///
+/// The answer is first checked against the [AnswerLedger] of the day, so that known wrong
+/// answers are not submitted twice, and the verdict is recorded in it afterward.
+pub fn submit_answer(year: usize, day: usize, part: Part, answer: &str) -> Result<Verdict> {
+    let mut ledger = AnswerLedger::open(year, day)?;
+    ledger
+        .check(part, answer)
+        .context("Answer refused by the local ledger")?;
+
+    let verdict = submit_answer_with(&Session::from_settings()?, year, day, part, answer)?;
+    ledger.record(part, answer, verdict.clone())?;
+
+    Ok(verdict)
+}
+
+/// Submit an answer to the answer endpoint of the given session's website
+pub fn submit_answer_with(
+    session: &Session,
+    year: usize,
+    day: usize,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict> {
+    info!(
+        "Submitting answer {:?} for {}/{} part {}",
+        answer, year, day, part
+    );
+    let res = session
+        .post_form(
+            &format!("{}/day/{}/answer", year, day),
+            &[
+                ("level", part.level().to_string()),
+                ("answer", answer.trim().to_string()),
+            ],
+        )
+        .context("Failed to send request")?;
+
+    match res.status() {
+        StatusCode::OK => parse_verdict(&res.text().context("Failed to read response body")?),
+        StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND => {
+            Err(anyhow!("Invalid or expired session"))
+        }
+        unhandled => match res.error_for_status() {
+            Ok(_) => Err(anyhow!("Expected to fail for status: {:?}", unhandled)),
+            Err(e) => Err(e).with_context(|| format!("Unhandled HTTP error: {:?}", unhandled)),
+        },
+    }
+}
+
+/// Interpret the HTML page returned after a submission
+pub fn parse_verdict(html: &str) -> Result<Verdict> {
+    let article: Regex = Regex::new(r"(?s)<article>(.*?)</article>").unwrap();
+    let tags: Regex = Regex::new(r"<[^>]*>").unwrap();
+
+    // The message is the only article of the page, fallback on the whole page otherwise
+    let message = article
+        .captures(html)
+        .and_then(|c| c.get(1))
+        .map_or(html, |m| m.as_str());
+    let message = tags.replace_all(message, "");
+    debug!("Submission response: {}", message);
+
+    if message.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if message.contains("Did you already complete it") {
+        Ok(Verdict::AlreadySolved)
+    } else if message.contains("You gave an answer too recently") {
+        Ok(Verdict::RateLimited(
+            parse_wait_time(&message).unwrap_or_default(),
+        ))
+    } else if message.contains("That's not the right answer") {
+        if message.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if message.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Wrong)
+        }
+    } else {
+        Err(anyhow!(
+            "Unrecognized submission response: {}",
+            message.trim()
+        ))
+    }
+}
+
+/// Extract "You have 1m 12s left to wait" into a duration
+fn parse_wait_time(message: &str) -> Option<Duration> {
+    let left: Regex = Regex::new(r"You have ((?:\d+[hms]\s*)+) left to wait").unwrap();
+    let unit: Regex = Regex::new(r"(\d+)([hms])").unwrap();
+
+    let left = left.captures(message)?.get(1)?.as_str();
+    let seconds = unit
+        .captures_iter(left)
+        .map(|c| {
+            let value: u64 = c[1].parse().unwrap_or(0);
+            match &c[2] {
+                "h" => value * 3600,
+                "m" => value * 60,
+                _ => value,
+            }
+        })
+        .sum();
+    Some(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::testing::serve_once;
+    use test_log::test;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    fn parse_correct() {
+        let html = page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::Correct);
+    }
+
+    #[test]
+    fn parse_too_high() {
+        let html = page("That's not the right answer; your answer is too high.  Please wait one minute before trying again.");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::TooHigh);
+    }
+
+    #[test]
+    fn parse_too_low() {
+        let html = page("That's not the right answer; your answer is too low.");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::TooLow);
+    }
+
+    #[test]
+    fn parse_wrong() {
+        let html = page("That's not the right answer.  If you're stuck, make sure you're using the full input data.");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::Wrong);
+    }
+
+    #[test]
+    fn parse_already_solved() {
+        let html =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::AlreadySolved);
+    }
+
+    #[test]
+    fn parse_rate_limited() {
+        let html = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait.");
+        assert_eq!(
+            parse_verdict(&html).unwrap(),
+            Verdict::RateLimited(Duration::from_secs(72))
+        );
+    }
+
+    #[test]
+    fn parse_unknown() {
+        assert!(parse_verdict(&page("Something else entirely")).is_err());
+    }
+
+    #[test]
+    fn submit_to_local_server() {
+        let (url, request) = serve_once(200, &page("That's the right answer!"));
+        let session = Session::new(url, "secret");
+
+        let verdict = submit_answer_with(&session, 2021, 5, Part::Two, "12").unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let request = request.join().unwrap();
+        assert!(request.starts_with("POST /2021/day/5/answer "));
+        assert!(request.contains("session=secret"));
+        assert!(request.ends_with("level=2&answer=12"));
+    }
+}
@@ -0,0 +1,57 @@
+//! Helpers for the tests: scratch files and a stand-in HTTP server for the website clients
+use std::io::BufReader;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::thread::JoinHandle;
+
+use super::mock::{read_request, write_response, Response};
+
+/// Location for the files of a test, cleared of what a previous run left behind
+pub(crate) fn scratch(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join("puzzling-tests").join(name);
+    let _ = std::fs::remove_dir_all(&path);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// Serve a single HTTP response on a random local port
+///
+/// Returns the base URL of the server and a handle resolving to the raw request received
+pub(crate) fn serve_once(status: u16, body: &str) -> (String, JoinHandle<String>) {
+    let (url, handle) = serve(&[(status, body)]);
+    (
+        url,
+        std::thread::spawn(move || handle.join().unwrap().remove(0)),
+    )
+}
+
+/// Serve the given responses, in order, one per connection
+pub(crate) fn serve(responses: &[(u16, &str)]) -> (String, JoinHandle<Vec<String>>) {
+    serve_responses(
+        responses
+            .iter()
+            .map(|(status, body)| Response::new(*status, *body))
+            .collect(),
+    )
+}
+
+/// Same as [serve], for responses with headers
+pub(crate) fn serve_responses(responses: Vec<Response>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("binding local test server");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = std::thread::spawn(move || {
+        responses
+            .into_iter()
+            .map(|response| {
+                let (stream, _) = listener.accept().expect("accepting test connection");
+                let request =
+                    read_request(&mut BufReader::new(&stream)).expect("reading test request");
+                write_response(&mut &stream, &response).expect("writing test response");
+                request.raw
+            })
+            .collect()
+    });
+
+    (url, handle)
+}
@@ -0,0 +1,326 @@
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use super::cache::{profile_namespace, Cache};
+use super::challenge::ChallengeError;
+use super::source::ChallengeSource;
+use crate::config::{active_profile, Settings};
+use crate::prelude::*;
+
+/// Environment variable holding the vault key, takes precedence over `vault_key`
+pub const KEY_VARIABLE: &str = "PUZZLING_VAULT_KEY";
+
+/// Size of the nonce prepended to each encrypted input
+const NONCE_SIZE: usize = 12;
+
+/// Encrypted copy of the inputs, safe to commit
+///
+/// Each input is stored as `<account>/<year>/<day>.bin`: a random nonce followed by the input
+/// encrypted with ChaCha20-Poly1305. The key is 32 random bytes, not a passphrase, since anyone
+/// can try to guess it offline. The account, year and day are authenticated along with the
+/// input, so an input moved to another day or account cannot be decrypted.
+pub struct Vault {
+    root: PathBuf,
+    account: String,
+    cipher: ChaCha20Poly1305,
+}
+
+/// Result of [Vault::verify] for one input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// Decrypted and identical to the cached input
+    Matching,
+    /// Decrypted, but not in the cache to compare with
+    Readable,
+    /// Decrypted, but different from the cached input
+    Mismatching,
+    /// Could not be decrypted: wrong key or corrupted file
+    Unreadable,
+}
+
+impl Vault {
+    /// Vault of an account, in its own directory of `directory`
+    pub fn new(directory: impl AsRef<Path>, account: &str, key: &Key) -> Self {
+        Self {
+            root: directory.as_ref().join(account),
+            account: account.into(),
+            cipher: ChaCha20Poly1305::new(key),
+        }
+    }
+
+    /// Vault of the selected profile in `vault_dir`, if a key is configured
+    pub fn from_settings() -> Result<Option<Self>> {
+        let settings = &Settings::get().advent_of_code;
+        let key = match std::env::var(KEY_VARIABLE)
+            .ok()
+            .or_else(|| settings.vault_key.clone())
+            .filter(|key| !key.is_empty())
+        {
+            Some(key) => parse_key(&key)?,
+            None => return Ok(None),
+        };
+        let account = profile_namespace(active_profile().as_deref(), &settings.base_url());
+        Ok(Some(Self::new(&settings.vault_dir, &account, &key)))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, year: usize, day: usize) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(day.to_string())
+            .with_extension("bin")
+    }
+
+    pub fn contains(&self, year: usize, day: usize) -> bool {
+        self.path(year, day).exists()
+    }
+
+    /// Data authenticated along with an input: where it belongs
+    fn associated_data(&self, year: usize, day: usize) -> String {
+        format!("{}/{}/{}", self.account, year, day)
+    }
+
+    /// Encrypt an input into the vault, replacing any previous value
+    pub fn add(&self, year: usize, day: usize, input: &str) -> Result<()> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = self.associated_data(year, day);
+        let payload = Payload {
+            msg: input.as_bytes(),
+            aad: aad.as_bytes(),
+        };
+        let encrypted = self
+            .cipher
+            .encrypt(&nonce, payload)
+            .map_err(|_| anyhow!("Encrypting input {}/{}", year, day))?;
+
+        let path = self.path(year, day);
+        std::fs::create_dir_all(path.parent().unwrap()).context("Creating vault directory")?;
+        std::fs::write(path, [nonce.as_slice(), &encrypted].concat()).context("Writing vault")
+    }
+
+    /// Decrypt an input from the vault
+    pub fn get(&self, year: usize, day: usize) -> Result<String, ChallengeError> {
+        let path = self.path(year, day);
+        if !path.exists() {
+            return Err(ChallengeError::Missing {
+                year,
+                day,
+                origin: self.root.display().to_string(),
+            });
+        }
+
+        let contents = std::fs::read(&path)?;
+        if contents.len() < NONCE_SIZE {
+            return Err(ChallengeError::VaultUnreadable(path));
+        }
+        let (nonce, encrypted) = contents.split_at(NONCE_SIZE);
+        let aad = self.associated_data(year, day);
+        let payload = Payload {
+            msg: encrypted,
+            aad: aad.as_bytes(),
+        };
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .ok()
+            .and_then(|decrypted| String::from_utf8(decrypted).ok())
+            .ok_or(ChallengeError::VaultUnreadable(path))
+    }
+
+    /// Every input stored in the vault, ordered by year and day
+    pub fn entries(&self) -> Result<Vec<(usize, usize)>> {
+        let mut entries = vec![];
+        if !self.root.exists() {
+            return Ok(entries);
+        }
+        for year in std::fs::read_dir(&self.root).context("Listing vault")? {
+            let year = year?.path();
+            let year_number = match year.file_name().and_then(|n| n.to_str()?.parse().ok()) {
+                Some(year_number) => year_number,
+                None => continue,
+            };
+            for file in std::fs::read_dir(&year).context("Listing vault year")? {
+                let day = file?
+                    .path()
+                    .file_name()
+                    .and_then(|n| n.to_str()?.strip_suffix(".bin")?.parse().ok());
+                if let Some(day) = day {
+                    entries.push((year_number, day));
+                }
+            }
+        }
+        entries.sort_unstable();
+        Ok(entries)
+    }
+
+    /// Copy inputs missing from the vault out of the cache, and the other way around
+    ///
+    /// Returns the number of inputs added to the vault and restored to the cache.
+    pub fn sync(&self, cache: &Cache) -> Result<(usize, usize)> {
+        let mut added = 0;
+        for entry in cache.entries()? {
+            if let Some((year, day)) = entry.challenge() {
+                if !self.contains(year, day) {
+                    self.add(year, day, &cache.read(&entry.path)?)?;
+                    added += 1;
+                }
+            }
+        }
+
+        let mut restored = 0;
+        for (year, day) in self.entries()? {
+            let path = Cache::challenge_path(year, day);
+            if !cache.root().join(&path).exists() {
+                cache.write(&path, &self.get(year, day)?)?;
+                restored += 1;
+            }
+        }
+        Ok((added, restored))
+    }
+
+    /// Check that every input of the vault can be decrypted and matches the cache
+    pub fn verify(&self, cache: &Cache) -> Result<Vec<(usize, usize, Verification)>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .map(|(year, day)| {
+                let verification = match (
+                    self.get(year, day),
+                    cache.read(Cache::challenge_path(year, day)),
+                ) {
+                    (Err(_), _) => Verification::Unreadable,
+                    (Ok(_), Err(_)) => Verification::Readable,
+                    (Ok(input), Ok(cached)) if input == cached => Verification::Matching,
+                    (Ok(_), Ok(_)) => Verification::Mismatching,
+                };
+                (year, day, verification)
+            })
+            .collect())
+    }
+}
+
+/// Parse a key written as 64 hexadecimal characters
+pub fn parse_key(hex: &str) -> Result<Key> {
+    let hex = hex.trim();
+    let invalid =
+        || anyhow!("The vault key must be 64 hexadecimal characters, see `advent vault keygen`");
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid())?;
+    Ok(*Key::from_slice(&bytes))
+}
+
+/// A new random key, in hexadecimal
+pub fn generate_key() -> String {
+    ChaCha20Poly1305::generate_key(&mut OsRng)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+impl ChallengeSource for Vault {
+    fn challenge(&self, year: usize, day: usize) -> Result<String, ChallengeError> {
+        let challenge = self.get(year, day)?;
+        info!("Challenge read from vault");
+        Ok(challenge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent_of_code::testing::scratch;
+    use test_log::test;
+
+    fn key() -> Key {
+        parse_key(&generate_key()).unwrap()
+    }
+
+    #[test]
+    fn encrypt_then_decrypt() {
+        let root = scratch("encrypt_then_decrypt");
+        let vault = Vault::new(&root, "default", &key());
+        vault.add(2021, 1, "199\n200\n").unwrap();
+
+        assert_eq!(vault.get(2021, 1).unwrap(), "199\n200\n");
+        assert_eq!(vault.entries().unwrap(), [(2021, 1)]);
+
+        let stored = std::fs::read(root.join("default").join("2021").join("1.bin")).unwrap();
+        assert!(!String::from_utf8_lossy(&stored).contains("199"));
+    }
+
+    #[test]
+    fn refuse_wrong_key() {
+        let root = scratch("refuse_wrong_key");
+        Vault::new(&root, "default", &key())
+            .add(2021, 1, "199")
+            .unwrap();
+
+        assert!(matches!(
+            Vault::new(&root, "default", &key()).get(2021, 1),
+            Err(ChallengeError::VaultUnreadable(_))
+        ));
+    }
+
+    #[test]
+    fn refuse_moved_input() {
+        let root = scratch("refuse_moved_input");
+        let key = key();
+        let vault = Vault::new(&root, "default", &key);
+        vault.add(2021, 1, "199").unwrap();
+
+        std::fs::copy(vault.path(2021, 1), vault.path(2021, 2)).unwrap();
+        assert!(matches!(
+            vault.get(2021, 2),
+            Err(ChallengeError::VaultUnreadable(_))
+        ));
+
+        let other = Vault::new(&root, "profile-alice", &key);
+        std::fs::create_dir_all(other.path(2021, 1).parent().unwrap()).unwrap();
+        std::fs::copy(vault.path(2021, 1), other.path(2021, 1)).unwrap();
+        assert!(matches!(
+            other.get(2021, 1),
+            Err(ChallengeError::VaultUnreadable(_))
+        ));
+    }
+
+    #[test]
+    fn parse_keys() {
+        let key = generate_key();
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, generate_key());
+        assert!(parse_key(&key).is_ok());
+
+        assert!(parse_key("correct horse battery staple").is_err());
+        assert!(parse_key(&key[..62]).is_err());
+        assert!(parse_key(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn sync_and_verify() {
+        let cache = Cache::new(scratch("sync_and_verify_cache"));
+        cache.write(Cache::challenge_path(2021, 1), "199").unwrap();
+        let vault = Vault::new(scratch("sync_and_verify_vault"), "default", &key());
+        vault.add(2021, 2, "forward 5").unwrap();
+
+        assert_eq!(vault.sync(&cache).unwrap(), (1, 1));
+        assert_eq!(
+            cache.read(Cache::challenge_path(2021, 2)).unwrap(),
+            "forward 5"
+        );
+        assert!(vault
+            .verify(&cache)
+            .unwrap()
+            .iter()
+            .all(|(_, _, v)| *v == Verification::Matching));
+    }
+}
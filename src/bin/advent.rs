@@ -1,5 +1,20 @@
 use clap::{crate_authors, crate_version, App, AppSettings, Arg, ArgMatches};
+use puzzling::advent_of_code::calendar::{self, Calendar, LocalSolution};
+use puzzling::advent_of_code::leaderboard::analytics::{self, Scoring};
+use puzzling::advent_of_code::leaderboard::{history, watch};
+use puzzling::advent_of_code::mock::MockSite;
+use puzzling::advent_of_code::source::{self, ChallengeSource, HttpSource};
+use puzzling::advent_of_code::vault::{self, Vault, Verification};
+use puzzling::advent_of_code::{
+    daily_challenge_when_available, description, leaderboard, prefetch, puzzle_description,
+    registry, samples, session, submit_answer, validate_session, AnswerLedger, Cache, Part,
+    Verdict,
+};
 use puzzling::prelude::*;
+
+/// Where the solutions live, relative to the root of the repository
+const SOLUTIONS: &str = "src/advent_of_code/solutions";
+
 fn main() {
     // SECTION: BOOTSTRAP
     puzzling::logging::initialize_logging();
@@ -16,9 +31,16 @@ fn main() {
             AppSettings::DisableHelpSubcommand,
             AppSettings::SubcommandRequiredElseHelp,
         ])
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .help("Account profile from puzzling.toml (or PUZZLING_PROFILE)")
+                .takes_value(true)
+                .global(true),
+        )
         .subcommand(
             App::new("prepare")
-                .about("Generate new bin using the templates/advent.rs file")
+                .about("Generate a new solution using the templates/advent.rs file")
                 .arg(
                     Arg::with_name("force")
                         .long("force")
@@ -26,6 +48,142 @@ fn main() {
                         .takes_value(false)
                         .global(true),
                 )
+                .arg(
+                    Arg::with_name("no-input")
+                        .long("no-input")
+                        .help("Do not wait for the input to be available")
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name("year")
+                        .help("Year of Advent of Code")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .validator(is_day)
+                        .help("Day of Advent of Code")
+                        .required(true)
+                        .index(2),
+                ),
+        )
+        .subcommand(
+            App::new("run")
+                .about("Run the solutions of a year, or of a single day")
+                .arg(
+                    Arg::with_name("year")
+                        .help("Year of Advent of Code")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .validator(is_day)
+                        .help("Day of Advent of Code, every solved day when omitted")
+                        .index(2),
+                )
+                .arg(
+                    Arg::with_name("part")
+                        .long("part")
+                        .short("p")
+                        .help("Only run this part (1 or 2)")
+                        .takes_value(true)
+                        .requires("day"),
+                )
+                .arg(
+                    Arg::with_name("input")
+                        .long("input")
+                        .help("Input file, directory laid out as <year>/<day>.txt, or -")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("submit")
+                        .long("submit")
+                        .help("Submit the answers to the website")
+                        .takes_value(false)
+                        .requires("day"),
+                ),
+        )
+        .subcommand(
+            App::new("fetch")
+                .about("Download inputs and descriptions into the cache")
+                .arg(
+                    Arg::with_name("year")
+                        .help("Year of Advent of Code")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .long("all")
+                        .help("Every unlocked day of the year")
+                        .required(true)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name("jobs")
+                        .long("jobs")
+                        .short("j")
+                        .help("Days fetched at once")
+                        .default_value("2"),
+                ),
+        )
+        .subcommand(
+            App::new("read")
+                .about("Read the puzzle description in the terminal")
+                .arg(
+                    Arg::with_name("year")
+                        .help("Year of Advent of Code")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .validator(is_day)
+                        .help("Day of Advent of Code")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::with_name("part")
+                        .long("part")
+                        .help("Only show this part (1 or 2)")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            App::new("submit")
+                .about("Submit an answer to Advent of Code")
+                .arg(
+                    Arg::with_name("year")
+                        .help("Year of Advent of Code")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .validator(is_day)
+                        .help("Day of Advent of Code")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::with_name("part")
+                        .help("Part of the puzzle (1 or 2)")
+                        .required(true)
+                        .index(3),
+                )
+                .arg(
+                    Arg::with_name("answer")
+                        .help("Answer to submit")
+                        .required(true)
+                        .index(4),
+                ),
+        )
+        .subcommand(
+            App::new("check")
+                .about("Run a solution against the input of every profile")
                 .arg(
                     Arg::with_name("year")
                         .help("Year of Advent of Code")
@@ -34,36 +192,731 @@ fn main() {
                 )
                 .arg(
                     Arg::with_name("day")
+                        .validator(is_day)
                         .help("Day of Advent of Code")
                         .required(true)
                         .index(2),
                 ),
         )
+        .subcommand(App::new("whoami").about("Check the session configured in puzzling.toml"))
+        .subcommand(
+            App::new("status")
+                .about("Show stars, local solutions and the answers submitted so far")
+                .arg(
+                    Arg::with_name("tests")
+                        .long("tests")
+                        .help("Run the tests of each local solution")
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name("year")
+                        .help("Year of Advent of Code")
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .validator(is_day)
+                        .help("Day of Advent of Code")
+                        .index(2),
+                ),
+        )
+        .subcommand(
+            App::new("cache")
+                .about("Manage the local cache of inputs")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(App::new("list").about("List cached files"))
+                .subcommand(
+                    App::new("purge")
+                        .about("Remove cached inputs, answers and leaderboard history are kept")
+                        .arg(
+                            Arg::with_name("volatile")
+                                .long("volatile")
+                                .help("Only remove expiring entries (leaderboards, ...)")
+                                .takes_value(false),
+                        )
+                        .arg(Arg::with_name("year").help("Only purge this year").index(1)),
+                )
+                .subcommand(
+                    App::new("export")
+                        .about("Bundle the cached inputs and answers in a JSON file")
+                        .arg(Arg::with_name("file").required(true).index(1)),
+                )
+                .subcommand(
+                    App::new("import")
+                        .about("Restore cached inputs and answers from a JSON file")
+                        .arg(
+                            Arg::with_name("force")
+                                .long("force")
+                                .help("Overwrite existing entries")
+                                .takes_value(false),
+                        )
+                        .arg(Arg::with_name("file").required(true).index(1)),
+                ),
+        )
+        .subcommand(
+            App::new("vault")
+                .about("Manage the encrypted inputs vault")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    App::new("add")
+                        .about("Encrypt the input of a day into the vault")
+                        .arg(
+                            Arg::with_name("year")
+                                .help("Year of Advent of Code")
+                                .required(true)
+                                .index(1),
+                        )
+                        .arg(
+                            Arg::with_name("day")
+                                .validator(is_day)
+                                .help("Day of Advent of Code")
+                                .required(true)
+                                .index(2),
+                        ),
+                )
+                .subcommand(App::new("sync").about("Copy inputs between the cache and the vault"))
+                .subcommand(App::new("keygen").about("Generate a random vault key"))
+                .subcommand(
+                    App::new("verify")
+                        .about("Check that the vault can be decrypted and matches the cache"),
+                ),
+        )
         .subcommand(
             App::new("leaderboard")
                 .about("Transform the leaderboard API to my liking")
-                .arg(Arg::with_name("id")),
+                .arg(
+                    Arg::with_name("id")
+                        .help("Identifier of the private leaderboard")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("year")
+                        .long("year")
+                        .help("Year of Advent of Code, defaults to the last event")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .validator(is_day)
+                        .long("day")
+                        .help("Show split times of a single day")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("scoring")
+                        .long("scoring")
+                        .help("Rank members by another scheme")
+                        .possible_values(&["local", "time", "median", "stars"])
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("watch")
+                        .long("watch")
+                        .help("Poll the leaderboard and report changes")
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name("sink")
+                        .long("sink")
+                        .help("Where to send changes: stdout, jsonl:PATH or webhook:URL")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .requires("watch"),
+                )
+                .subcommand(
+                    App::new("history")
+                        .about("Show how the leaderboard evolved over time")
+                        .arg(
+                            Arg::with_name("format")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["text", "csv", "json", "svg", "html"])
+                                .default_value("text"),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .long("output")
+                                .short("o")
+                                .help("Write to a file instead of the standard output")
+                                .takes_value(true),
+                        ),
+                ),
+        )
+        .subcommand(
+            App::new("mock")
+                .about("Serve fixtures as an offline Advent of Code website")
+                .arg(
+                    Arg::with_name("fixtures")
+                        .help("Fixtures directory, defaults to the ones bundled with the crate")
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("address")
+                        .long("address")
+                        .help("Address to listen on")
+                        .default_value("127.0.0.1:8080"),
+                )
+                .arg(
+                    Arg::with_name("session")
+                        .long("session")
+                        .help("Session cookie accepted by the mock")
+                        .default_value("mock"),
+                ),
         )
         .get_matches();
 
+    if let Some(profile) = matches.value_of("profile") {
+        if let Err(e) = puzzling::config::select_profile(profile) {
+            error!("{}", e);
+            return;
+        }
+    }
+
     match matches.subcommand_name() {
         Some("prepare") => subcommand_prepare(matches),
+        Some("run") => subcommand_run(matches),
+        Some("fetch") => subcommand_fetch(matches),
+        Some("read") => subcommand_read(matches),
+        Some("submit") => subcommand_submit(matches),
+        Some("check") => subcommand_check(matches),
+        Some("whoami") => subcommand_whoami(),
+        Some("status") => subcommand_status(matches),
+        Some("cache") => subcommand_cache(matches),
+        Some("vault") => subcommand_vault(matches),
         Some("leaderboard") => subcommand_leaderboard(matches),
+        Some("mock") => subcommand_mock(matches),
         Some(_) => unreachable!("All subcommands MUST are described"),
         None => unreachable!("SubcommandRequiredElseHelp MUST prevent this branch"),
     };
 }
 
+/// Validate a day argument, puzzles are published from the 1st to the 25th
+fn is_day(day: String) -> Result<(), String> {
+    match day.parse::<usize>() {
+        Ok(1..=25) => Ok(()),
+        _ => Err(format!("{} is not a day of Advent of Code (1 to 25)", day)),
+    }
+}
+
+fn subcommand_run(matches: ArgMatches) {
+    let command = matches.subcommand_matches("run").unwrap();
+
+    let year: usize = command
+        .value_of("year")
+        .unwrap()
+        .parse()
+        .expect("invalid year");
+    let day: Option<usize> = command
+        .value_of("day")
+        .map(|day| day.parse().expect("invalid day"));
+    let part: Option<Part> = command
+        .value_of("part")
+        .map(|part| part.parse().expect("invalid part"));
+
+    let input = command.value_of("input");
+    if day.is_none() && input.map_or(false, source::is_single_input) {
+        error!("A single input cannot be used for a whole year, use a directory");
+        std::process::exit(1);
+    }
+
+    let solutions = registry::find(year, day);
+    if solutions.is_empty() {
+        error!(
+            "No solution for {}",
+            command
+                .value_of("day")
+                .map_or(year.to_string(), |day| format!("{}/{}", year, day))
+        );
+        std::process::exit(1);
+    }
+
+    let submit = command.is_present("submit");
+    let mut failed = 0;
+    for solution in &solutions {
+        info!("<bold>{}/{:02}</>", solution.year, solution.day);
+        if !(solution.run)(input, part, submit) {
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        error!("{} of {} days failed", failed, solutions.len());
+        std::process::exit(1);
+    }
+}
+
+fn subcommand_fetch(matches: ArgMatches) {
+    let command = matches.subcommand_matches("fetch").unwrap();
+
+    let year: usize = command
+        .value_of("year")
+        .unwrap()
+        .parse()
+        .expect("invalid year");
+    let jobs: usize = command
+        .value_of("jobs")
+        .unwrap()
+        .parse()
+        .expect("invalid jobs");
+    match prefetch::prefetch_year(year, jobs) {
+        Ok(report) => {
+            info!(
+                "Fetched {} days, {} already cached",
+                report.fetched.len(),
+                report.skipped.len()
+            );
+            for (day, e) in report.failed {
+                error!("Day {}: {}", day, e);
+            }
+        }
+        Err(e) => error!("Could not prefetch {}: {:?}", year, e),
+    }
+}
+
+fn subcommand_read(matches: ArgMatches) {
+    let command = matches.subcommand_matches("read").unwrap();
+
+    let year: usize = command
+        .value_of("year")
+        .unwrap()
+        .parse()
+        .expect("invalid year");
+    let day: usize = command
+        .value_of("day")
+        .unwrap()
+        .parse()
+        .expect("invalid day");
+    let part: Option<Part> = command
+        .value_of("part")
+        .map(|p| p.parse().expect("invalid part"));
+
+    let description = match puzzle_description(year, day) {
+        Ok(description) => description,
+        Err(e) => {
+            error!("Could not read description: {:?}", e);
+            return;
+        }
+    };
+
+    for (index, article) in description.articles.iter().enumerate() {
+        if part.map_or(false, |p| p.level() as usize != index + 1) {
+            continue;
+        }
+        println!("{}\n", description::render_terminal(article));
+    }
+}
+
+fn subcommand_submit(matches: ArgMatches) {
+    let command = matches.subcommand_matches("submit").unwrap();
+
+    let year: usize = command
+        .value_of("year")
+        .unwrap()
+        .parse()
+        .expect("invalid year");
+    let day: usize = command
+        .value_of("day")
+        .unwrap()
+        .parse()
+        .expect("invalid day");
+    let part: Part = command
+        .value_of("part")
+        .unwrap()
+        .parse()
+        .expect("invalid part");
+    let answer = command.value_of("answer").unwrap();
+
+    match submit_answer(year, day, part, answer) {
+        Ok(Verdict::Correct) => info!("<green>That's the right answer!</>"),
+        Ok(Verdict::RateLimited(wait)) => {
+            warn!("Answer submitted too recently, wait {}s", wait.as_secs())
+        }
+        Ok(verdict) => warn!("Answer {}: {}", answer, verdict),
+        Err(e) => error!("Could not submit answer: {:?}", e),
+    }
+}
+
+fn subcommand_check(matches: ArgMatches) {
+    let command = matches.subcommand_matches("check").unwrap();
+
+    let year: usize = command
+        .value_of("year")
+        .unwrap()
+        .parse()
+        .expect("invalid year");
+    let day: usize = command
+        .value_of("day")
+        .unwrap()
+        .parse()
+        .expect("invalid day");
+
+    // The selected profile, every profile otherwise
+    let profiles = match puzzling::config::active_profile() {
+        Some(profile) => vec![profile],
+        None => puzzling::config::profiles(),
+    };
+    if profiles.is_empty() {
+        warn!("No profile configured in puzzling.toml");
+        return;
+    }
+
+    for profile in profiles {
+        info!("<bold>Profile {}</>", profile);
+
+        // Settings are loaded once per process, so each profile gets its own
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["run", &year.to_string(), &day.to_string()])
+            .env(puzzling::config::PROFILE_VARIABLE, &profile)
+            .output()
+            .expect("could not run the solution");
+        if !output.status.success() {
+            error!(
+                "Solution failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            continue;
+        }
+
+        let cache = Cache::for_profile(Some(&profile));
+        let ledger = AnswerLedger::open_at(AnswerLedger::path_in(&cache, year, day))
+            .expect("could not read answer ledger");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for part in [Part::One, Part::Two] {
+            let answer = stdout
+                .lines()
+                .find_map(|line| line.strip_prefix(&format!("Part {}: ", part)))
+                .map(str::trim);
+            match (answer, ledger.solution(part)) {
+                (None, _) => warn!("  Part {}: no answer", part),
+                (Some(answer), Some(solution)) if answer == solution => {
+                    info!("  Part {}: <green>{}</>", part, answer)
+                }
+                (Some(answer), Some(solution)) => {
+                    error!("  Part {}: {} (expected {})", part, answer, solution)
+                }
+                (Some(answer), None) => info!("  Part {}: {} (not verified)", part, answer),
+            }
+        }
+    }
+}
+
+fn subcommand_whoami() {
+    match validate_session() {
+        Ok(status) => {
+            info!("Logged in as <bold>{}</>", status.account.name);
+            if status.account.supporter {
+                info!("AoC++ supporter");
+            }
+            if let Some(stars) = status.account.stars {
+                info!("Stars: {}", stars);
+            }
+            info!(
+                "Session first seen {}, estimated expiry {}",
+                status.first_seen.format("%Y-%m-%d"),
+                status.estimated_expiry.format("%Y-%m-%d")
+            );
+        }
+        Err(e) => error!("{:?}", e),
+    }
+}
+
+fn subcommand_status(matches: ArgMatches) {
+    let command = matches.subcommand_matches("status").unwrap();
+
+    let year: Option<usize> = command
+        .value_of("year")
+        .map(|y| y.parse().expect("invalid year"));
+    let day: Option<usize> = command
+        .value_of("day")
+        .map(|d| d.parse().expect("invalid day"));
+
+    // Star grid of each year, with the local solutions
+    let calendar = calendar::calendar().unwrap_or_else(|e| {
+        warn!("Could not fetch calendar: {:?}", e);
+        Calendar::default()
+    });
+    let cwd = std::env::current_dir().unwrap();
+    let local = calendar::local_solutions(cwd.join(SOLUTIONS)).unwrap_or_default();
+    let years = calendar
+        .years
+        .keys()
+        .cloned()
+        .chain(local.iter().map(|(y, _)| *y))
+        .filter(|y| year.map_or(true, |year| year == *y))
+        .sorted()
+        .dedup()
+        .collect_vec();
+
+    for grid_year in years.into_iter().rev() {
+        let grid = calendar::render_year(&calendar, grid_year, |y, d| {
+            if !local.contains(&(y, d)) {
+                LocalSolution::Missing
+            } else if !command.is_present("tests") {
+                LocalSolution::Present
+            } else if solution_tests_pass(y, d) {
+                LocalSolution::Passing
+            } else {
+                LocalSolution::Failing
+            }
+        });
+        println!("{}", paris::formatter::colorize_string(grid));
+    }
+
+    let ledgers = AnswerLedger::all().expect("could not read answer ledgers");
+    for (ledger_year, ledger_day, ledger) in ledgers {
+        if year.map_or(false, |y| y != ledger_year) || day.map_or(false, |d| d != ledger_day) {
+            continue;
+        }
+
+        info!("<bold>{}/{:02}</>", ledger_year, ledger_day);
+        for part in [Part::One, Part::Two] {
+            let guesses = ledger.guesses(part).collect_vec();
+            if guesses.is_empty() {
+                continue;
+            }
+            match ledger.solution(part) {
+                Some(solution) => info!("  Part {}: <green>{}</>", part, solution),
+                None => info!("  Part {}: unsolved", part),
+            }
+            for guess in guesses {
+                info!("    {:>20} {}", guess.answer, guess.verdict);
+            }
+        }
+    }
+}
+
+/// Run the tests of a local solution, with its completed tests enabled
+fn solution_tests_pass(year: usize, day: u32) -> bool {
+    info!("Testing advent_{:04}_{:02}", year, day);
+    std::process::Command::new("cargo")
+        .args(["test", "--quiet", "--features"])
+        .arg(format!("advent_{},completed", year))
+        .arg("--lib")
+        .arg(format!("advent_{:04}_{:02}::", year, day))
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_or(false, |status| status.success())
+}
+
+fn subcommand_cache(matches: ArgMatches) {
+    let command = matches.subcommand_matches("cache").unwrap();
+    let cache = Cache::from_settings();
+    info!("Cache directory: {:?}", cache.root());
+
+    match command.subcommand() {
+        ("list", Some(_)) => {
+            for entry in cache.entries().expect("could not list cache") {
+                let kind = if entry.volatile { "volatile" } else { "" };
+                info!("{:>10} {:>8} {}", entry.size, kind, entry.path.display());
+            }
+        }
+        ("purge", Some(purge)) => {
+            let year = purge
+                .value_of("year")
+                .map(|y| y.parse().expect("invalid year"));
+            let removed = cache
+                .purge(purge.is_present("volatile"), year)
+                .expect("could not purge cache");
+            info!("Removed {} files", removed);
+        }
+        ("export", Some(export)) => {
+            let exported = cache
+                .export(export.value_of("file").unwrap())
+                .expect("could not export cache");
+            info!("Exported {} files", exported);
+        }
+        ("import", Some(import)) => {
+            let imported = cache
+                .import(import.value_of("file").unwrap(), import.is_present("force"))
+                .expect("could not import cache");
+            info!("Imported {} files", imported);
+        }
+        _ => unreachable!("SubcommandRequiredElseHelp MUST prevent this branch"),
+    }
+}
+
+fn subcommand_vault(matches: ArgMatches) {
+    let command = matches.subcommand_matches("vault").unwrap();
+    if command.subcommand_matches("keygen").is_some() {
+        info!("Vault key: {}", vault::generate_key());
+        info!("Keep it secret, set it as vault_key in puzzling.toml or PUZZLING_VAULT_KEY");
+        return;
+    }
+
+    let vault = match Vault::from_settings() {
+        Ok(Some(vault)) => vault,
+        Ok(None) => {
+            error!("No vault key, set vault_key in puzzling.toml or PUZZLING_VAULT_KEY");
+            return;
+        }
+        Err(e) => {
+            error!("{:#}", e);
+            return;
+        }
+    };
+    let cache = Cache::from_settings();
+    info!("Vault directory: {:?}", vault.root());
+
+    match command.subcommand() {
+        ("add", Some(add)) => {
+            let year: usize = add.value_of("year").unwrap().parse().expect("invalid year");
+            let day: usize = add.value_of("day").unwrap().parse().expect("invalid day");
+            let input = HttpSource::from_settings()
+                .expect("could not read settings")
+                .challenge(year, day)
+                .expect("could not fetch input");
+            vault
+                .add(year, day, &input)
+                .expect("could not add input to vault");
+            info!("Added {}/{} to the vault", year, day);
+        }
+        ("sync", Some(_)) => {
+            let (added, restored) = vault.sync(&cache).expect("could not sync vault");
+            info!(
+                "Added {} inputs to the vault, restored {} to the cache",
+                added, restored
+            );
+        }
+        ("verify", Some(_)) => {
+            for (year, day, verification) in vault.verify(&cache).expect("could not verify vault") {
+                match verification {
+                    Verification::Matching => info!("{}/{:02}: <green>ok</>", year, day),
+                    Verification::Readable => info!("{}/{:02}: ok (not cached)", year, day),
+                    Verification::Mismatching => {
+                        error!("{}/{:02}: differs from the cached input", year, day)
+                    }
+                    Verification::Unreadable => error!("{}/{:02}: cannot be decrypted", year, day),
+                }
+            }
+        }
+        _ => unreachable!("SubcommandRequiredElseHelp MUST prevent this branch"),
+    }
+}
+
 fn subcommand_leaderboard(matches: ArgMatches) {
     let command = matches.subcommand_matches("leaderboard").unwrap();
-    info!("{:?}", command)
+
+    let id: u64 = command.value_of("id").unwrap().parse().expect("invalid id");
+    let year: usize = command
+        .value_of("year")
+        .map_or_else(leaderboard::current_event, |y| {
+            y.parse().expect("invalid year")
+        });
+
+    let day: Option<u32> = command
+        .value_of("day")
+        .map(|d| d.parse().expect("invalid day"));
+    let scoring: Option<Scoring> = command
+        .value_of("scoring")
+        .map(|s| s.parse().expect("invalid scoring"));
+
+    if let Some(history) = command.subcommand_matches("history") {
+        return leaderboard_history(year, id, history);
+    }
+
+    if command.is_present("watch") {
+        if let Err(e) = session::ensure_valid_session() {
+            error!("{:?}", e);
+            return;
+        }
+        let mut sinks = command
+            .values_of("sink")
+            .map_or(vec!["stdout"], |sinks| sinks.collect())
+            .into_iter()
+            .map(|spec| watch::sink_from_spec(spec).expect("invalid sink"))
+            .collect_vec();
+        if let Err(e) = watch::watch(year, id, &mut sinks) {
+            error!("Stopped watching leaderboard: {:?}", e);
+        }
+        return;
+    }
+
+    let board = match leaderboard::leaderboard(year, id) {
+        Ok(board) => board,
+        Err(e) => {
+            error!("Could not fetch leaderboard: {:?}", e);
+            return;
+        }
+    };
+
+    if let Some(day) = day {
+        let splits = analytics::day_splits(&board, day);
+        println!("{}\n", analytics::render_splits(&splits));
+    }
+
+    let output = match scoring {
+        Some(scoring) => {
+            let ranked = analytics::rank(&board, scoring, day.map(|d| d..=d));
+            analytics::render_ranking(&ranked)
+        }
+        None if day.is_some() => return,
+        None => leaderboard::render_table(&board),
+    };
+    println!("{}", paris::formatter::colorize_string(output));
+}
+
+fn leaderboard_history(year: usize, id: u64, command: &ArgMatches) {
+    let snapshots = history::snapshots(&Cache::from_settings(), year, id)
+        .expect("could not read leaderboard snapshots");
+    info!("Found {} snapshots", snapshots.len());
+    let points = history::history(&snapshots);
+
+    let output = match command.value_of("format").unwrap() {
+        "csv" => history::to_csv(&points),
+        "json" => history::to_json(&points).expect("could not serialize history"),
+        "svg" => history::to_svg(&points),
+        "html" => history::to_html(&points, &format!("Leaderboard {} - {}", id, year)),
+        _ => history::to_text(&points),
+    };
+
+    match command.value_of("output") {
+        Some(file) => std::fs::write(file, output).expect("could not write history"),
+        None => println!("{}", output),
+    }
+}
+
+fn subcommand_mock(matches: ArgMatches) {
+    let command = matches.subcommand_matches("mock").unwrap();
+
+    let session = command.value_of("session").unwrap();
+    let site = match command.value_of("fixtures") {
+        Some(fixtures) => MockSite::new(fixtures, session),
+        None => MockSite::bundled(session),
+    };
+
+    match site.start_on(command.value_of("address").unwrap()) {
+        Ok(server) => {
+            info!("Serving on <bold>{}</>", server.url());
+            info!(
+                "Use it with {}={} and session_secret = \"{}\"",
+                puzzling::config::BASE_URL_VARIABLE,
+                server.url(),
+                session
+            );
+            if let Err(e) = server.join() {
+                error!("{:?}", e);
+            }
+        }
+        Err(e) => error!("Could not start mock server: {:?}", e),
+    }
 }
 
 fn subcommand_prepare(matches: ArgMatches) {
     let command = matches.subcommand_matches("prepare").unwrap();
 
-    let year = command.value_of("year").unwrap();
-    let day = command.value_of("day").unwrap();
+    let year: usize = command
+        .value_of("year")
+        .unwrap()
+        .parse()
+        .expect("invalid year");
+    let day: usize = command
+        .value_of("day")
+        .unwrap()
+        .parse()
+        .expect("invalid day");
 
     let cwd = std::env::current_dir().unwrap();
 
@@ -80,8 +933,7 @@ fn subcommand_prepare(matches: ArgMatches) {
         .replace("DD", format!("{:02}", day).as_str());
 
     let target_file = cwd
-        .join("src")
-        .join("bin")
+        .join(SOLUTIONS)
         .join(format!("advent_{:04}_{:02}", year, day))
         .with_extension("rs");
 
@@ -91,5 +943,35 @@ fn subcommand_prepare(matches: ArgMatches) {
         return;
     }
 
-    std::fs::write(target_file, contents).unwrap()
+    std::fs::write(&target_file, contents).unwrap();
+
+    if command.is_present("no-input") {
+        return;
+    }
+
+    // Have the input in cache before the first run of the new solution
+    match daily_challenge_when_available(year, day) {
+        Ok(challenge) => info!("Input ready: {} lines", challenge.lines().count()),
+        Err(e) => {
+            error!("Could not fetch input: {}", e);
+            return;
+        }
+    }
+
+    // Then fill the test sections with the examples of the description
+    let samples = match puzzle_description(year, day) {
+        Ok(description) => samples::extract_samples(&description),
+        Err(e) => {
+            error!("Could not fetch description: {:?}", e);
+            return;
+        }
+    };
+    info!("Found {} samples", samples.len());
+
+    let contents = std::fs::read_to_string(&target_file).unwrap();
+    std::fs::write(
+        &target_file,
+        samples::insert_sample_tests(&contents, &samples),
+    )
+    .unwrap();
 }
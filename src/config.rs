@@ -1,5 +1,6 @@
 /// Settings for the crate
-use std::lazy::SyncLazy;
+use std::collections::BTreeMap;
+use std::lazy::{SyncLazy, SyncOnceCell};
 
 use crate::prelude::*;
 use config::{Config, File};
@@ -9,15 +10,129 @@ use serde_derive::Deserialize;
 #[derive(Debug, Deserialize)]
 #[serde(default)]
 pub(crate) struct AdventOfCode {
+    /// Root of the website, `PUZZLING_BASE_URL` takes precedence
+    pub base_url: String,
+    /// Root of the cache, defaults to the XDG cache directory
+    pub cache_dir: Option<String>,
+    /// Time to live, in seconds, of volatile cache entries (inputs never expire)
     pub cache_time: u64,
+    /// Where inputs are read from: `http` or a directory laid out as `<year>/<day>.txt`
+    pub input: Option<String>,
+    pub session_secret: String,
+    /// Email or URL put in the User-Agent, so the website can reach you about your requests
+    pub contact: Option<String>,
+    /// Replaces the whole User-Agent
+    pub user_agent: Option<String>,
+    /// Minimum time, in milliseconds, between two requests to the website
+    pub request_interval: u64,
+    /// Retries of GET requests failing with a transient error, answers are never resent
+    pub request_retries: u32,
+    /// Estimated lifetime, in days, of a session cookie
+    pub session_lifetime: u64,
+    /// Named accounts, selected with the `PUZZLING_PROFILE` environment variable
+    pub profiles: BTreeMap<String, Profile>,
+    /// Encrypted inputs directory, safe to commit
+    pub vault_dir: String,
+    /// Key of the vault, 64 hexadecimal characters from `advent vault keygen`,
+    /// `PUZZLING_VAULT_KEY` takes precedence
+    pub vault_key: Option<String>,
+}
+
+/// AdventOfCode account settings
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(default)]
+pub(crate) struct Profile {
     pub session_secret: String,
 }
 
 impl std::default::Default for AdventOfCode {
     fn default() -> Self {
         Self {
+            base_url: DEFAULT_BASE_URL.into(),
+            cache_dir: None,
             cache_time: 3600,
+            input: None,
             session_secret: "".into(),
+            contact: None,
+            user_agent: None,
+            request_interval: 1000,
+            request_retries: 3,
+            session_lifetime: 30,
+            profiles: BTreeMap::new(),
+            vault_dir: "inputs.vault".into(),
+            vault_key: None,
+        }
+    }
+}
+
+/// Environment variable holding the name of the selected profile
+pub const PROFILE_VARIABLE: &str = "PUZZLING_PROFILE";
+
+/// The real website
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable overriding the website, e.g. to use `advent mock`
+pub const BASE_URL_VARIABLE: &str = "PUZZLING_BASE_URL";
+
+/// Name of the selected profile, `None` for the top level `session_secret`
+///
+/// The profile given to [select_profile] if any, `PUZZLING_PROFILE` otherwise.
+pub fn active_profile() -> Option<String> {
+    Settings::get().profile.get().cloned().or_else(|| {
+        std::env::var(PROFILE_VARIABLE)
+            .ok()
+            .filter(|name| !name.is_empty())
+    })
+}
+
+/// Select a profile for the rest of the process, once at startup (e.g. from `--profile`)
+///
+/// Child processes do not inherit it, pass [PROFILE_VARIABLE] to them explicitly.
+pub fn select_profile(name: &str) -> Result<()> {
+    let settings = Settings::get();
+    settings.advent_of_code.profile(name)?;
+    settings
+        .profile
+        .set(name.to_string())
+        .map_err(|_| anyhow!("A profile is already selected"))
+}
+
+/// Names of the configured profiles
+pub fn profiles() -> Vec<String> {
+    Settings::get()
+        .advent_of_code
+        .profiles
+        .keys()
+        .cloned()
+        .collect()
+}
+
+impl AdventOfCode {
+    /// Root of the website, from the environment when set
+    pub fn base_url(&self) -> String {
+        std::env::var(BASE_URL_VARIABLE)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| self.base_url.clone())
+    }
+
+    /// Settings of a profile, the error lists the known profiles
+    pub fn profile(&self, name: &str) -> Result<&Profile> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known = self.profiles.keys().join(", ");
+            anyhow!(
+                "Unknown profile: {} (known profiles: {})",
+                name,
+                if known.is_empty() { "none" } else { &known }
+            )
+        })
+    }
+
+    /// Session secret of the selected profile
+    pub fn session_secret(&self) -> Result<String> {
+        match active_profile() {
+            Some(name) => Ok(self.profile(&name)?.session_secret.clone()),
+            None => Ok(self.session_secret.clone()),
         }
     }
 }
@@ -55,6 +170,9 @@ impl Into<log::LevelFilter> for Logging {
 pub struct Settings {
     pub(crate) advent_of_code: AdventOfCode,
     pub(crate) logging: Logging,
+    /// Profile selected at startup, see [select_profile]
+    #[serde(skip)]
+    profile: SyncOnceCell<String>,
 }
 
 static SETTINGS: SyncLazy<Settings> = SyncLazy::new(|| {
@@ -88,3 +206,27 @@ impl Settings {
         config.try_deserialize().context("Deserializing settings")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn unknown_profile_lists_known() {
+        let mut settings = AdventOfCode::default();
+        assert!(settings
+            .profile("alice")
+            .unwrap_err()
+            .to_string()
+            .contains("known profiles: none"));
+
+        settings.profiles.insert("alice".into(), Profile::default());
+        settings.profiles.insert("bob".into(), Profile::default());
+        assert!(settings.profile("alice").is_ok());
+        assert_eq!(
+            settings.profile("alcie").unwrap_err().to_string(),
+            "Unknown profile: alcie (known profiles: alice, bob)"
+        );
+    }
+}
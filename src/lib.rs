@@ -1,4 +1,7 @@
 #![feature(never_type, once_cell)]
+// Used by the solutions of Advent of Code
+#![allow(incomplete_features)]
+#![feature(generators, generator_trait, type_alias_impl_trait, let_chains)]
 
 // Global exports
 pub mod config;
@@ -8,3 +11,10 @@ pub mod prelude;
 // Per challenge source modules
 #[cfg(feature = "advent")]
 pub mod advent_of_code;
+
+// Used by the registration macros
+#[doc(hidden)]
+pub use inventory;
+
+// Code generated by the derive macros refers to `::puzzling`, including in this crate
+extern crate self as puzzling;
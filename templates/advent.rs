@@ -1,12 +1,19 @@
 #![allow(dead_code, unused_variables)]
-use puzzling::advent_of_code::{daily_challenge, parsing, AdventOfCode};
+use puzzling::advent_of_code::{parsing, AdventInput, AdventOfCode};
 use puzzling::prelude::*;
 
-/// Input type for each line
-type Input = String;
+/// Input type for each line, parsed following its format
+#[derive(Debug, Clone, AdventInput)]
+#[advent(format = "{line}")]
+struct Input {
+    line: String,
+}
+
+/// Expected output of part one
+type AnswerOne = usize;
 
-/// Expected output
-type Answer = usize;
+/// Expected output of part two
+type AnswerTwo = usize;
 
 /// Representation of a complete puzzle
 #[derive(Debug, Clone)]
@@ -35,17 +42,24 @@ impl From<Vec<Input>> for Puzzle {
 
 impl AdventOfCode for Puzzle {
     type Input = Input;
-    type Answer = Answer;
+    type AnswerOne = AnswerOne;
+    type AnswerTwo = AnswerTwo;
     type Puzzle = Puzzle;
+    /// Work shared by both parts, computed once by `prepare`
+    type State = ();
+
+    fn prepare(&self) -> Result<Self::State> {
+        Ok(())
+    }
 
-    fn part_one(&self) -> Self::Answer {
+    fn part_one(&self, state: &Self::State) -> Result<Self::AnswerOne> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
         todo!()
     }
 
-    fn part_two(&self) -> Self::Answer {
+    fn part_two(&self, state: &Self::State) -> Result<Self::AnswerTwo> {
         #[cfg(test)]
         debug!("Puzzle: {:?}", self);
 
@@ -53,20 +67,7 @@ impl AdventOfCode for Puzzle {
     }
 }
 
-// TODO(macro): make bootstrap code injected by macro
-fn main() {
-    // SECTION: BOOTSTRAP
-    puzzling::logging::initialize_logging();
-    // !SECTION
-
-    let puzzle: Puzzle = daily_challenge(YYYY, DD)
-        .expect("impossible to fetch daily challenge")
-        .parse()
-        .expect("failed to parse daily challenge");
-
-    println!("Part 1: {}", puzzle.part_one());
-    println!("Part 2: {}", puzzle.part_two());
-}
+puzzling::advent_solution!(YYYY, DD, Puzzle);
 
 #[cfg(test)]
 #[cfg(feature = "advent_YYYY")]
@@ -75,7 +76,7 @@ mod tests {
 
     #[allow(unused_imports)]
     mod advent_YYYY_DD {
-        use crate::Puzzle;
+        use super::super::Puzzle;
         use puzzling::{advent_of_code::AdventOfCode, prelude::*};
         use test_log::test;
 